@@ -0,0 +1,41 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use green_kernels::laplace_3d::{evaluate_laplace_one_target, Laplace3dKernel};
+use green_kernels::traits::Kernel;
+use green_kernels::types::EvalType;
+use rand::prelude::*;
+
+fn benchmark_laplace_one_source(c: &mut Criterion) {
+    let ntargets = 100000;
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let source: Vec<f64> = (0..3).map(|_| rng.gen()).collect();
+    let targets: Vec<f64> = (0..3 * ntargets).map(|_| rng.gen()).collect();
+    let charges = vec![1.0];
+    let mut result = vec![0.0; ntargets];
+
+    let kernel = Laplace3dKernel::<f64>::new();
+
+    let mut group = c.benchmark_group("Laplace one source");
+
+    group.bench_function("evaluate_st", |b| {
+        b.iter(|| kernel.evaluate_st(EvalType::Value, &source, &targets, &charges, &mut result))
+    });
+
+    group.bench_function("one target at a time", |b| {
+        b.iter(|| {
+            for (target_index, my_result) in result.chunks_exact_mut(1).enumerate() {
+                let target = [
+                    targets[target_index],
+                    targets[ntargets + target_index],
+                    targets[2 * ntargets + target_index],
+                ];
+                evaluate_laplace_one_target(EvalType::Value, &target, &source, &charges, my_result)
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_laplace_one_source);
+criterion_main!(benches);
@@ -1,6 +1,7 @@
 //! Implementation of the Helmholtz kernel
 use crate::helpers::{
-    check_dimensions_assemble, check_dimensions_assemble_diagonal, check_dimensions_evaluate,
+    check_dimensions_assemble, check_dimensions_assemble_diagonal,
+    check_dimensions_assemble_scaled, check_dimensions_evaluate,
 };
 use crate::traits::Kernel;
 use crate::types::EvalType;
@@ -13,8 +14,11 @@ use std::marker::PhantomData;
 /// Kernel for Helmholtz in 3D
 #[derive(Clone, Default)]
 pub struct Helmholtz3dKernel<T: RlstScalar> {
-    /// Wavenumber
+    /// Real part of the wavenumber. Use [Helmholtz3dKernel::complex_wavenumber] to
+    /// obtain the full wavenumber including the attenuation.
     pub wavenumber: T::Real,
+    /// Imaginary part of the wavenumber. A positive value models attenuation in lossy media.
+    pub wavenumber_imag: T::Real,
     _phantom_t: std::marker::PhantomData<T>,
 }
 
@@ -23,9 +27,105 @@ impl<T: RlstScalar> Helmholtz3dKernel<T> {
     pub fn new(wavenumber: T::Real) -> Self {
         Self {
             wavenumber,
+            wavenumber_imag: <T::Real as Zero>::zero(),
             _phantom_t: PhantomData,
         }
     }
+
+    /// Create new with a complex wavenumber
+    ///
+    /// For a wavenumber `k = k_re + i k_im` the kernel `exp(i k r) / (4 pi r)` decays
+    /// like `exp(-k_im r)`. A purely imaginary wavenumber gives the Yukawa kernel.
+    pub fn new_complex(wavenumber: T) -> Self {
+        Self {
+            wavenumber: wavenumber.re(),
+            wavenumber_imag: wavenumber.im(),
+            _phantom_t: PhantomData,
+        }
+    }
+
+    /// The wavenumber as complex number
+    pub fn complex_wavenumber(&self) -> T::Complex {
+        T::complex(self.wavenumber, self.wavenumber_imag)
+    }
+}
+
+impl<T: RlstScalar<Complex = T>> Helmholtz3dKernel<T> {
+    /// Evaluate the far field pattern of the potential generated by the given sources.
+    ///
+    /// For a unit direction `d` the potential at `x = r d` behaves like
+    /// `exp(i k r) / r * F(d)` as `r` tends to infinity, where the far field pattern is
+    /// `F(d) = 1 / (4 pi) sum_j q_j exp(-i k d . y_j)` for sources `y_j` with charges `q_j`.
+    /// For a complex wavenumber the factor `exp(i k r)` also contains the decay `exp(-k_im r)`.
+    ///
+    /// - `sources`: The source points in the same layout as for [Kernel::evaluate_st].
+    /// - `charges`: The charges, one for each source point.
+    /// - `directions`: The unit directions in the same layout as the source points.
+    /// - `result`: The far field pattern, one value for each direction.
+    pub fn far_field_pattern(
+        &self,
+        sources: &[<T as RlstScalar>::Real],
+        charges: &[T],
+        directions: &[<T as RlstScalar>::Real],
+        result: &mut [T],
+    ) {
+        let nsources = charges.len();
+        let ndirections = result.len();
+        assert_eq!(
+            sources.len(),
+            3 * nsources,
+            "Wrong dimension for `sources`. {} != {} ",
+            sources.len(),
+            3 * nsources,
+        );
+        assert_eq!(
+            directions.len(),
+            3 * ndirections,
+            "Wrong dimension for `directions`. {} != {} ",
+            directions.len(),
+            3 * ndirections,
+        );
+
+        let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+        let wavenumber_re = self.wavenumber;
+        let wavenumber_im = self.wavenumber_imag;
+
+        let sources0 = &sources[0..nsources];
+        let sources1 = &sources[nsources..2 * nsources];
+        let sources2 = &sources[2 * nsources..3 * nsources];
+
+        for (direction_index, my_result) in result.iter_mut().enumerate() {
+            let direction = [
+                directions[direction_index],
+                directions[ndirections + direction_index],
+                directions[2 * ndirections + direction_index],
+            ];
+
+            let mut my_result_real = <T::Real as Zero>::zero();
+            let mut my_result_imag = <T::Real as Zero>::zero();
+
+            for index in 0..nsources {
+                let projection = direction[0] * sources0[index]
+                    + direction[1] * sources1[index]
+                    + direction[2] * sources2[index];
+
+                // exp(-i k d . y) for a possibly complex wavenumber k.
+                let phase = wavenumber_re * projection;
+                let scale = <T::Real as RlstScalar>::exp(wavenumber_im * projection);
+                let e_re = <T::Real as RlstScalar>::cos(phase) * scale;
+                let e_im = -<T::Real as RlstScalar>::sin(phase) * scale;
+
+                let charge_re = charges[index].re();
+                let charge_im = charges[index].im();
+
+                my_result_real += e_re * charge_re - e_im * charge_im;
+                my_result_imag += e_re * charge_im + e_im * charge_re;
+            }
+
+            *my_result =
+                <T as RlstScalar>::complex(my_result_real, my_result_imag).mul_real(m_inv_4pi);
+        }
+    }
 }
 
 impl<T: RlstScalar<Complex = T> + Send + Sync> Kernel for Helmholtz3dKernel<T>
@@ -56,6 +156,18 @@ where
         let ntargets = targets.len() / self.space_dimension();
         let range_dim = self.range_component_count(eval_type);
 
+        if charges.len() == 1 {
+            evaluate_helmholtz_one_source_complex(
+                eval_type,
+                sources,
+                targets,
+                charges[0],
+                self.complex_wavenumber(),
+                result,
+            );
+            return;
+        }
+
         result
             .chunks_exact_mut(range_dim)
             .enumerate()
@@ -66,17 +178,92 @@ where
                     targets[2 * ntargets + target_index],
                 ];
 
-                evaluate_helmholtz_one_target(
+                evaluate_helmholtz_one_target_complex(
                     eval_type,
                     &target,
                     sources,
                     charges,
-                    self.wavenumber,
+                    self.complex_wavenumber(),
                     my_chunk,
                 )
             });
     }
 
+    fn evaluate_st_multi_query(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        charges: &[Self::T],
+        query_sets: &[&[<Self::T as RlstScalar>::Real]],
+        outs: &mut [&mut [Self::T]],
+    ) {
+        assert_eq!(
+            query_sets.len(),
+            outs.len(),
+            "Number of query sets and outputs do not match. {} != {} ",
+            query_sets.len(),
+            outs.len(),
+        );
+        for (targets, result) in query_sets.iter().zip(outs.iter()) {
+            check_dimensions_evaluate(self, eval_type, sources, targets, charges, result);
+        }
+
+        let nsources = charges.len();
+        let range_dim = self.range_component_count(eval_type);
+        let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+        let one_real = <T::Real as num::One>::one();
+        let wavenumber = self.complex_wavenumber();
+        let lossy = self.wavenumber_imag != <T::Real as Zero>::zero();
+
+        // Split the sources and scale the charges once for all query sets.
+        let split_sources = [
+            &sources[0..nsources],
+            &sources[nsources..2 * nsources],
+            &sources[2 * nsources..3 * nsources],
+        ];
+        let scaled_charges = charges
+            .iter()
+            .map(|charge| charge.mul_real(m_inv_4pi))
+            .collect::<Vec<_>>();
+
+        for (targets, result) in query_sets.iter().zip(outs.iter_mut()) {
+            let ntargets = targets.len() / self.space_dimension();
+
+            result
+                .chunks_exact_mut(range_dim)
+                .enumerate()
+                .for_each(|(target_index, my_chunk)| {
+                    let target = [
+                        targets[target_index],
+                        targets[ntargets + target_index],
+                        targets[2 * ntargets + target_index],
+                    ];
+
+                    if lossy {
+                        evaluate_helmholtz_one_target_impl::<T, true>(
+                            eval_type,
+                            &target,
+                            split_sources,
+                            &scaled_charges,
+                            wavenumber,
+                            one_real,
+                            my_chunk,
+                        )
+                    } else {
+                        evaluate_helmholtz_one_target_impl::<T, false>(
+                            eval_type,
+                            &target,
+                            split_sources,
+                            &scaled_charges,
+                            wavenumber,
+                            one_real,
+                            my_chunk,
+                        )
+                    }
+                });
+        }
+    }
+
     fn evaluate_mt(
         &self,
         eval_type: EvalType,
@@ -99,12 +286,12 @@ where
                     targets[2 * ntargets + target_index],
                 ];
 
-                evaluate_helmholtz_one_target(
+                evaluate_helmholtz_one_target_complex(
                     eval_type,
                     &target,
                     sources,
                     charges,
-                    self.wavenumber,
+                    self.complex_wavenumber(),
                     my_chunk,
                 )
             });
@@ -133,14 +320,23 @@ where
         };
 
         let kr: T::Real = diff_norm * self.wavenumber;
+        let (kr_im, decay) = if self.wavenumber_imag == zero_real {
+            (zero_real, one_real)
+        } else {
+            let kr_im: T::Real = diff_norm * self.wavenumber_imag;
+            (kr_im, <T::Real as RlstScalar>::exp(-kr_im))
+        };
         match eval_type {
             EvalType::Value => {
-                result[0] = T::complex(kr.cos(), kr.sin()).mul_real(inv_diff_norm * m_inv_4pi)
+                result[0] = T::complex(kr.cos() * decay, kr.sin() * decay)
+                    .mul_real(inv_diff_norm * m_inv_4pi)
             }
             EvalType::ValueDeriv => {
                 let inv_diff_norm_squared = inv_diff_norm * inv_diff_norm;
-                let gr = T::complex(kr.cos(), kr.sin()).mul_real(inv_diff_norm * m_inv_4pi);
-                let gr_diff = gr.mul_real(inv_diff_norm_squared) * T::complex(one_real, -kr);
+                let gr = T::complex(kr.cos() * decay, kr.sin() * decay)
+                    .mul_real(inv_diff_norm * m_inv_4pi);
+                let gr_diff =
+                    gr.mul_real(inv_diff_norm_squared) * T::complex(one_real + kr_im, -kr);
 
                 result[0] = gr;
                 result[1] = gr_diff.mul_real(diff0);
@@ -172,16 +368,66 @@ where
                     targets[2 * ntargets + target_index],
                 ];
 
-                assemble_helmholtz_one_target(
+                assemble_helmholtz_one_target_complex(
                     eval_type,
                     &target,
                     sources,
-                    self.wavenumber,
+                    self.complex_wavenumber(),
                     my_chunk,
                 )
             });
     }
 
+    fn assemble_st_scaled(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        targets: &[<Self::T as RlstScalar>::Real],
+        target_scales: &[Self::T],
+        result: &mut [Self::T],
+    ) {
+        check_dimensions_assemble_scaled(self, eval_type, sources, targets, target_scales, result);
+        let ntargets = targets.len() / self.space_dimension();
+        let nsources = sources.len() / self.space_dimension();
+        let range_dim = self.range_component_count(eval_type);
+
+        result
+            .chunks_exact_mut(range_dim * nsources)
+            .zip(target_scales)
+            .enumerate()
+            .for_each(|(target_index, (my_chunk, scale))| {
+                let target = [
+                    targets[target_index],
+                    targets[ntargets + target_index],
+                    targets[2 * ntargets + target_index],
+                ];
+
+                assemble_helmholtz_one_target_complex(
+                    eval_type,
+                    &target,
+                    sources,
+                    self.complex_wavenumber(),
+                    my_chunk,
+                );
+
+                my_chunk.iter_mut().for_each(|value| *value *= *scale);
+            });
+    }
+
+    fn assemble_symmetric_st(
+        &self,
+        points: &[<Self::T as RlstScalar>::Real],
+        result: &mut [Self::T],
+    ) {
+        check_dimensions_assemble(self, EvalType::Value, points, points, result);
+
+        if self.wavenumber_imag == <T::Real as Zero>::zero() {
+            assemble_helmholtz_symmetric_impl::<T, false>(points, self.complex_wavenumber(), result)
+        } else {
+            assemble_helmholtz_symmetric_impl::<T, true>(points, self.complex_wavenumber(), result)
+        }
+    }
+
     fn assemble_mt(
         &self,
         eval_type: EvalType,
@@ -204,11 +450,11 @@ where
                     targets[2 * ntargets + target_index],
                 ];
 
-                assemble_helmholtz_one_target(
+                assemble_helmholtz_one_target_complex(
                     eval_type,
                     &target,
                     sources,
-                    self.wavenumber,
+                    self.complex_wavenumber(),
                     my_chunk,
                 )
             });
@@ -257,15 +503,67 @@ pub fn evaluate_helmholtz_one_target<T: RlstScalar<Complex = T>>(
     wavenumber: T::Real,
     result: &mut [T],
 ) {
-    let ncharges = charges.len();
-    let nsources = ncharges;
+    evaluate_helmholtz_one_target_complex(
+        eval_type,
+        target,
+        sources,
+        charges,
+        T::from_real(wavenumber),
+        result,
+    )
+}
+
+/// Evaluate Helmholtz kernel for one target with a complex wavenumber
+///
+/// A positive imaginary part of the wavenumber models attenuation in lossy media.
+pub fn evaluate_helmholtz_one_target_complex<T: RlstScalar<Complex = T>>(
+    eval_type: EvalType,
+    target: &[<T as RlstScalar>::Real],
+    sources: &[<T as RlstScalar>::Real],
+    charges: &[T],
+    wavenumber: T,
+    result: &mut [T],
+) {
+    let nsources = charges.len();
     let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+    let sources = [
+        &sources[0..nsources],
+        &sources[nsources..2 * nsources],
+        &sources[2 * nsources..3 * nsources],
+    ];
+
+    // Dispatch once on the wavenumber so that the inner loops stay free of branches
+    // and are the same as for the lossless kernel if the wavenumber is real.
+    if wavenumber.im() == <T::Real as Zero>::zero() {
+        evaluate_helmholtz_one_target_impl::<T, false>(
+            eval_type, target, sources, charges, wavenumber, m_inv_4pi, result,
+        )
+    } else {
+        evaluate_helmholtz_one_target_impl::<T, true>(
+            eval_type, target, sources, charges, wavenumber, m_inv_4pi, result,
+        )
+    }
+}
+
+/// Implementation of [evaluate_helmholtz_one_target_complex] for a real (`LOSSY = false`) or complex wavenumber.
+///
+/// The sources are split into their components and the kernel values are multiplied by `scale`.
+fn evaluate_helmholtz_one_target_impl<T: RlstScalar<Complex = T>, const LOSSY: bool>(
+    eval_type: EvalType,
+    target: &[<T as RlstScalar>::Real],
+    sources: [&[<T as RlstScalar>::Real]; 3],
+    charges: &[T],
+    wavenumber: T,
+    scale: T::Real,
+    result: &mut [T],
+) {
+    let nsources = charges.len();
     let zero_real = <T::Real as num::Zero>::zero();
     let one_real = <T::Real as num::One>::one();
+    let wavenumber_re = wavenumber.re();
+    let wavenumber_im = wavenumber.im();
 
-    let sources0 = &sources[0..nsources];
-    let sources1 = &sources[nsources..2 * nsources];
-    let sources2 = &sources[2 * nsources..3 * nsources];
+    let [sources0, sources1, sources2] = sources;
 
     let mut diff0: T::Real;
     let mut diff1: T::Real;
@@ -288,18 +586,23 @@ pub fn evaluate_helmholtz_one_target<T: RlstScalar<Complex = T>>(
                     }
                 };
 
-                let kr = wavenumber * diff_norm;
+                let kr = wavenumber_re * diff_norm;
+                let decay = if LOSSY {
+                    <T::Real as RlstScalar>::exp(-wavenumber_im * diff_norm)
+                } else {
+                    one_real
+                };
 
-                let g_re = <T::Real as RlstScalar>::cos(kr) * inv_diff_norm;
-                let g_im = <T::Real as RlstScalar>::sin(kr) * inv_diff_norm;
+                let g_re = <T::Real as RlstScalar>::cos(kr) * decay * inv_diff_norm;
+                let g_im = <T::Real as RlstScalar>::sin(kr) * decay * inv_diff_norm;
                 let charge_re = charges[index].re();
                 let charge_im = charges[index].im();
 
                 my_result_imag += g_re * charge_im + g_im * charge_re;
                 my_result_real += g_re * charge_re - g_im * charge_im;
             }
-            result[0] += <T::Complex as RlstScalar>::complex(my_result_real, my_result_imag)
-                .mul_real(m_inv_4pi);
+            result[0] +=
+                <T::Complex as RlstScalar>::complex(my_result_real, my_result_imag).mul_real(scale);
         }
         EvalType::ValueDeriv => {
             // Cannot simply use an array my_result as this is not
@@ -329,12 +632,22 @@ pub fn evaluate_helmholtz_one_target<T: RlstScalar<Complex = T>>(
                 };
                 let inv_diff_norm_squared = inv_diff_norm * inv_diff_norm;
 
-                let kr = wavenumber * diff_norm;
-                let g_re = <T::Real as RlstScalar>::cos(kr) * inv_diff_norm * m_inv_4pi;
-                let g_im = <T::Real as RlstScalar>::sin(kr) * inv_diff_norm * m_inv_4pi;
+                let kr = wavenumber_re * diff_norm;
+                let kr_im = if LOSSY {
+                    wavenumber_im * diff_norm
+                } else {
+                    zero_real
+                };
+                let decay = if LOSSY {
+                    <T::Real as RlstScalar>::exp(-kr_im)
+                } else {
+                    one_real
+                };
+                let g_re = <T::Real as RlstScalar>::cos(kr) * decay * inv_diff_norm * scale;
+                let g_im = <T::Real as RlstScalar>::sin(kr) * decay * inv_diff_norm * scale;
 
-                let g_deriv_im = (g_im - g_re * kr) * inv_diff_norm_squared;
-                let g_deriv_re = (g_re + g_im * kr) * inv_diff_norm_squared;
+                let g_deriv_im = (g_im * (one_real + kr_im) - g_re * kr) * inv_diff_norm_squared;
+                let g_deriv_re = (g_re * (one_real + kr_im) + g_im * kr) * inv_diff_norm_squared;
 
                 let charge_re = charges[index].re();
                 let charge_im = charges[index].im();
@@ -363,6 +676,166 @@ pub fn evaluate_helmholtz_one_target<T: RlstScalar<Complex = T>>(
     }
 }
 
+/// Evaluate Helmholtz kernel for one source
+///
+/// This is the counterpart of [evaluate_helmholtz_one_target] for a single source and many targets.
+/// The targets are given in the same layout as for [Kernel::evaluate_st].
+pub fn evaluate_helmholtz_one_source<T: RlstScalar<Complex = T>>(
+    eval_type: EvalType,
+    source: &[<T as RlstScalar>::Real],
+    targets: &[<T as RlstScalar>::Real],
+    charge: T,
+    wavenumber: T::Real,
+    result: &mut [T],
+) {
+    evaluate_helmholtz_one_source_complex(
+        eval_type,
+        source,
+        targets,
+        charge,
+        T::from_real(wavenumber),
+        result,
+    )
+}
+
+/// Evaluate Helmholtz kernel for one source with a complex wavenumber
+///
+/// A positive imaginary part of the wavenumber models attenuation in lossy media.
+pub fn evaluate_helmholtz_one_source_complex<T: RlstScalar<Complex = T>>(
+    eval_type: EvalType,
+    source: &[<T as RlstScalar>::Real],
+    targets: &[<T as RlstScalar>::Real],
+    charge: T,
+    wavenumber: T,
+    result: &mut [T],
+) {
+    if wavenumber.im() == <T::Real as Zero>::zero() {
+        evaluate_helmholtz_one_source_impl::<T, false>(
+            eval_type, source, targets, charge, wavenumber, result,
+        )
+    } else {
+        evaluate_helmholtz_one_source_impl::<T, true>(
+            eval_type, source, targets, charge, wavenumber, result,
+        )
+    }
+}
+
+/// Implementation of [evaluate_helmholtz_one_source_complex] for a real (`LOSSY = false`) or complex wavenumber.
+fn evaluate_helmholtz_one_source_impl<T: RlstScalar<Complex = T>, const LOSSY: bool>(
+    eval_type: EvalType,
+    source: &[<T as RlstScalar>::Real],
+    targets: &[<T as RlstScalar>::Real],
+    charge: T,
+    wavenumber: T,
+    result: &mut [T],
+) {
+    assert_eq!(source.len(), 3);
+    assert_eq!(targets.len() % 3, 0);
+    let ntargets = targets.len() / 3;
+    let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+    let zero_real = <T::Real as num::Zero>::zero();
+    let one_real = <T::Real as num::One>::one();
+    let wavenumber_re = wavenumber.re();
+    let wavenumber_im = wavenumber.im();
+
+    let targets0 = &targets[0..ntargets];
+    let targets1 = &targets[ntargets..2 * ntargets];
+    let targets2 = &targets[2 * ntargets..3 * ntargets];
+
+    let charge_re = charge.re() * m_inv_4pi;
+    let charge_im = charge.im() * m_inv_4pi;
+
+    let mut diff0: T::Real;
+    let mut diff1: T::Real;
+    let mut diff2: T::Real;
+
+    match eval_type {
+        EvalType::Value => {
+            for (index, my_result) in result.iter_mut().enumerate() {
+                diff0 = source[0] - targets0[index];
+                diff1 = source[1] - targets1[index];
+                diff2 = source[2] - targets2[index];
+                let diff_norm = (diff0 * diff0 + diff1 * diff1 + diff2 * diff2).sqrt();
+                let inv_diff_norm = {
+                    if diff_norm == zero_real {
+                        zero_real
+                    } else {
+                        one_real / diff_norm
+                    }
+                };
+
+                let kr = wavenumber_re * diff_norm;
+                let decay = if LOSSY {
+                    <T::Real as RlstScalar>::exp(-wavenumber_im * diff_norm)
+                } else {
+                    one_real
+                };
+
+                let g_re = <T::Real as RlstScalar>::cos(kr) * decay * inv_diff_norm;
+                let g_im = <T::Real as RlstScalar>::sin(kr) * decay * inv_diff_norm;
+
+                *my_result += <T as RlstScalar>::complex(
+                    g_re * charge_re - g_im * charge_im,
+                    g_re * charge_im + g_im * charge_re,
+                );
+            }
+        }
+        EvalType::ValueDeriv => {
+            for (index, my_result) in result.chunks_exact_mut(4).enumerate() {
+                diff0 = source[0] - targets0[index];
+                diff1 = source[1] - targets1[index];
+                diff2 = source[2] - targets2[index];
+                let diff_norm = (diff0 * diff0 + diff1 * diff1 + diff2 * diff2).sqrt();
+                let inv_diff_norm = {
+                    if diff_norm == zero_real {
+                        zero_real
+                    } else {
+                        one_real / diff_norm
+                    }
+                };
+                let inv_diff_norm_squared = inv_diff_norm * inv_diff_norm;
+
+                let kr = wavenumber_re * diff_norm;
+                let kr_im = if LOSSY {
+                    wavenumber_im * diff_norm
+                } else {
+                    zero_real
+                };
+                let decay = if LOSSY {
+                    <T::Real as RlstScalar>::exp(-kr_im)
+                } else {
+                    one_real
+                };
+                let g_re = <T::Real as RlstScalar>::cos(kr) * decay * inv_diff_norm;
+                let g_im = <T::Real as RlstScalar>::sin(kr) * decay * inv_diff_norm;
+
+                let g_deriv_im = (g_im * (one_real + kr_im) - g_re * kr) * inv_diff_norm_squared;
+                let g_deriv_re = (g_re * (one_real + kr_im) + g_im * kr) * inv_diff_norm_squared;
+
+                let times_charge_imag = g_deriv_re * charge_im + g_deriv_im * charge_re;
+                let times_charge_real = g_deriv_re * charge_re - g_deriv_im * charge_im;
+
+                my_result[0] += <T as RlstScalar>::complex(
+                    g_re * charge_re - g_im * charge_im,
+                    g_re * charge_im + g_im * charge_re,
+                );
+                my_result[1] += <T as RlstScalar>::complex(
+                    times_charge_real * diff0,
+                    times_charge_imag * diff0,
+                );
+                my_result[2] += <T as RlstScalar>::complex(
+                    times_charge_real * diff1,
+                    times_charge_imag * diff1,
+                );
+                my_result[3] += <T as RlstScalar>::complex(
+                    times_charge_real * diff2,
+                    times_charge_imag * diff2,
+                );
+            }
+        }
+    }
+}
+
 /// Assemble Helmholtz kernel for one target
 pub fn assemble_helmholtz_one_target<T: RlstScalar<Complex = T>>(
     eval_type: EvalType,
@@ -370,6 +843,44 @@ pub fn assemble_helmholtz_one_target<T: RlstScalar<Complex = T>>(
     sources: &[<T as RlstScalar>::Real],
     wavenumber: T::Real,
     result: &mut [T],
+) {
+    assemble_helmholtz_one_target_complex(
+        eval_type,
+        target,
+        sources,
+        T::from_real(wavenumber),
+        result,
+    )
+}
+
+/// Assemble Helmholtz kernel for one target with a complex wavenumber
+///
+/// A positive imaginary part of the wavenumber models attenuation in lossy media.
+pub fn assemble_helmholtz_one_target_complex<T: RlstScalar<Complex = T>>(
+    eval_type: EvalType,
+    target: &[<T as RlstScalar>::Real],
+    sources: &[<T as RlstScalar>::Real],
+    wavenumber: T,
+    result: &mut [T],
+) {
+    if wavenumber.im() == <T::Real as Zero>::zero() {
+        assemble_helmholtz_one_target_impl::<T, false>(
+            eval_type, target, sources, wavenumber, result,
+        )
+    } else {
+        assemble_helmholtz_one_target_impl::<T, true>(
+            eval_type, target, sources, wavenumber, result,
+        )
+    }
+}
+
+/// Implementation of [assemble_helmholtz_one_target_complex] for a real (`LOSSY = false`) or complex wavenumber.
+fn assemble_helmholtz_one_target_impl<T: RlstScalar<Complex = T>, const LOSSY: bool>(
+    eval_type: EvalType,
+    target: &[<T as RlstScalar>::Real],
+    sources: &[<T as RlstScalar>::Real],
+    wavenumber: T,
+    result: &mut [T],
 ) {
     assert_eq!(sources.len() % 3, 0);
     assert_eq!(target.len(), 3);
@@ -377,6 +888,8 @@ pub fn assemble_helmholtz_one_target<T: RlstScalar<Complex = T>>(
     let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
     let zero_real = <T::Real as num::Zero>::zero();
     let one_real = <T::Real as num::One>::one();
+    let wavenumber_re = wavenumber.re();
+    let wavenumber_im = wavenumber.im();
 
     let sources0 = &sources[0..nsources];
     let sources1 = &sources[nsources..2 * nsources];
@@ -401,10 +914,15 @@ pub fn assemble_helmholtz_one_target<T: RlstScalar<Complex = T>>(
                     }
                 };
 
-                let kr = wavenumber * diff_norm;
+                let kr = wavenumber_re * diff_norm;
+                let decay = if LOSSY {
+                    <T::Real as RlstScalar>::exp(-wavenumber_im * diff_norm)
+                } else {
+                    one_real
+                };
 
-                let g_re = <T::Real as RlstScalar>::cos(kr) * inv_diff_norm * m_inv_4pi;
-                let g_im = <T::Real as RlstScalar>::sin(kr) * inv_diff_norm * m_inv_4pi;
+                let g_re = <T::Real as RlstScalar>::cos(kr) * decay * inv_diff_norm * m_inv_4pi;
+                let g_im = <T::Real as RlstScalar>::sin(kr) * decay * inv_diff_norm * m_inv_4pi;
 
                 result[index] = <T as RlstScalar>::complex(g_re, g_im);
             }
@@ -448,12 +966,22 @@ pub fn assemble_helmholtz_one_target<T: RlstScalar<Complex = T>>(
                 };
                 let inv_diff_norm_squared = inv_diff_norm * inv_diff_norm;
 
-                let kr = wavenumber * diff_norm;
-                let g_re = <T::Real as RlstScalar>::cos(kr) * inv_diff_norm * m_inv_4pi;
-                let g_im = <T::Real as RlstScalar>::sin(kr) * inv_diff_norm * m_inv_4pi;
+                let kr = wavenumber_re * diff_norm;
+                let kr_im = if LOSSY {
+                    wavenumber_im * diff_norm
+                } else {
+                    zero_real
+                };
+                let decay = if LOSSY {
+                    <T::Real as RlstScalar>::exp(-kr_im)
+                } else {
+                    one_real
+                };
+                let g_re = <T::Real as RlstScalar>::cos(kr) * decay * inv_diff_norm * m_inv_4pi;
+                let g_im = <T::Real as RlstScalar>::sin(kr) * decay * inv_diff_norm * m_inv_4pi;
 
-                let g_deriv_im = (g_im - g_re * kr) * inv_diff_norm_squared;
-                let g_deriv_re = (g_re + g_im * kr) * inv_diff_norm_squared;
+                let g_deriv_im = (g_im * (one_real + kr_im) - g_re * kr) * inv_diff_norm_squared;
+                let g_deriv_re = (g_re * (one_real + kr_im) + g_im * kr) * inv_diff_norm_squared;
 
                 my_result1_real = g_deriv_re * diff0;
                 my_result1_imag = g_deriv_im * diff0;
@@ -478,6 +1006,54 @@ pub fn assemble_helmholtz_one_target<T: RlstScalar<Complex = T>>(
     }
 }
 
+/// Assemble the symmetric Helmholtz kernel matrix of a set of points with itself.
+///
+/// Only the upper triangle is computed and mirrored. `LOSSY` selects at compile time
+/// whether the decay for a complex wavenumber is computed.
+fn assemble_helmholtz_symmetric_impl<T: RlstScalar<Complex = T>, const LOSSY: bool>(
+    points: &[<T as RlstScalar>::Real],
+    wavenumber: T,
+    result: &mut [T],
+) {
+    let npoints = points.len() / 3;
+    let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+    let zero_real = <T::Real as num::Zero>::zero();
+    let one_real = <T::Real as num::One>::one();
+    let wavenumber_real = wavenumber.re();
+    let wavenumber_imag = wavenumber.im();
+
+    let points0 = &points[0..npoints];
+    let points1 = &points[npoints..2 * npoints];
+    let points2 = &points[2 * npoints..3 * npoints];
+
+    for target_index in 0..npoints {
+        for source_index in target_index..npoints {
+            let diff0 = points0[source_index] - points0[target_index];
+            let diff1 = points1[source_index] - points1[target_index];
+            let diff2 = points2[source_index] - points2[target_index];
+            let diff_norm = (diff0 * diff0 + diff1 * diff1 + diff2 * diff2).sqrt();
+            let inv_diff_norm = {
+                if diff_norm == zero_real {
+                    zero_real
+                } else {
+                    one_real / diff_norm
+                }
+            };
+
+            let kr = wavenumber_real * diff_norm;
+            let decay = if LOSSY {
+                <T::Real as RlstScalar>::exp(-wavenumber_imag * diff_norm)
+            } else {
+                one_real
+            };
+            let value =
+                T::complex(kr.cos() * decay, kr.sin() * decay).mul_real(inv_diff_norm * m_inv_4pi);
+            result[target_index * npoints + source_index] = value;
+            result[source_index * npoints + target_index] = value;
+        }
+    }
+}
+
 fn helmholtz_component_count(eval_type: EvalType) -> usize {
     match eval_type {
         EvalType::Value => 1,
@@ -666,181 +1242,566 @@ mod test {
     }
 
     #[test]
-    fn test_assemble_helmholtz_3d() {
-        let nsources = 3;
-        let ntargets = 5;
-        let wavenumber: f64 = 1.5;
+    fn test_helmholtz_3d_complex_wavenumber() {
+        let eps = 1E-8;
+
+        let nsources = 5;
+        let ntargets = 3;
 
         let mut sources = rlst::rlst_dynamic_array2!(f64, [nsources, 3]);
         let mut targets = rlst::rlst_dynamic_array2!(f64, [ntargets, 3]);
+        let mut charges = rlst::rlst_dynamic_array1!(c64, [nsources]);
 
-        sources.fill_from_seed_equally_distributed(1);
-        targets.fill_from_seed_equally_distributed(2);
+        sources.fill_from_seed_equally_distributed(0);
+        targets.fill_from_seed_equally_distributed(1);
+        charges.fill_from_seed_equally_distributed(2);
+
+        // A real wavenumber given as complex number reproduces the lossless kernel.
+
+        let mut lossless = rlst::rlst_dynamic_array2!(c64, [4, ntargets]);
+        let mut real_complex = rlst::rlst_dynamic_array2!(c64, [4, ntargets]);
+
+        Helmholtz3dKernel::<c64>::new(1.5).evaluate_st(
+            EvalType::ValueDeriv,
+            sources.data(),
+            targets.data(),
+            charges.data(),
+            lossless.data_mut(),
+        );
+        Helmholtz3dKernel::<c64>::new_complex(c64::complex(1.5, 0.0)).evaluate_st(
+            EvalType::ValueDeriv,
+            sources.data(),
+            targets.data(),
+            charges.data(),
+            real_complex.data_mut(),
+        );
 
-        let mut green_value_t = rlst::rlst_dynamic_array2!(c64, [nsources, ntargets]);
+        for target_index in 0..ntargets {
+            for deriv_index in 0..4 {
+                assert_relative_eq!(
+                    real_complex[[deriv_index, target_index]],
+                    lossless[[deriv_index, target_index]],
+                    epsilon = 1E-14
+                );
+            }
+        }
 
-        Helmholtz3dKernel::<c64>::new(wavenumber).assemble_st(
+        // A purely imaginary wavenumber gives the Yukawa kernel.
+
+        let decay: f64 = 2.0;
+        let mut green_value = rlst::rlst_dynamic_array1!(c64, [ntargets]);
+
+        Helmholtz3dKernel::<c64>::new_complex(c64::complex(0.0, decay)).evaluate_st(
             EvalType::Value,
             sources.data(),
             targets.data(),
-            green_value_t.data_mut(),
+            charges.data(),
+            green_value.data_mut(),
         );
 
-        // The matrix needs to be transposed so that the first row corresponds to the first target,
-        // second row to the second target and so on.
+        for target_index in 0..ntargets {
+            let mut expected = c64::default();
+            for source_index in 0..nsources {
+                let dist = ((targets[[target_index, 0]] - sources[[source_index, 0]]).square()
+                    + (targets[[target_index, 1]] - sources[[source_index, 1]]).square()
+                    + (targets[[target_index, 2]] - sources[[source_index, 2]]).square())
+                .sqrt();
+
+                expected +=
+                    charges[[source_index]] * (-decay * dist).exp() * 0.25 * f64::FRAC_1_PI()
+                        / dist;
+            }
+
+            assert_relative_eq!(green_value[[target_index]], expected, epsilon = 1E-12);
+        }
 
-        let mut green_value = rlst::rlst_dynamic_array2!(c64, [ntargets, nsources]);
-        green_value.fill_from(green_value_t.transpose());
+        // Derivatives for a general complex wavenumber are checked by finite differences.
 
-        for charge_index in 0..nsources {
-            let mut charges = rlst::rlst_dynamic_array1![c64, [nsources]];
-            let mut expected = rlst::rlst_dynamic_array1![c64, [ntargets]];
-            charges[[charge_index]] = c64::complex(1.0, 0.0);
+        let kernel = Helmholtz3dKernel::<c64>::new_complex(c64::complex(1.5, 0.7));
+
+        let mut green_value = rlst::rlst_dynamic_array1!(c64, [ntargets]);
+        let mut expected = rlst::rlst_dynamic_array2!(c64, [4, ntargets]);
 
-            Helmholtz3dKernel::<c64>::new(wavenumber).evaluate_st(
+        kernel.evaluate_st(
+            EvalType::Value,
+            sources.data(),
+            targets.data(),
+            charges.data(),
+            green_value.data_mut(),
+        );
+        kernel.evaluate_st(
+            EvalType::ValueDeriv,
+            sources.data(),
+            targets.data(),
+            charges.data(),
+            expected.data_mut(),
+        );
+
+        for component in 0..3 {
+            let mut targets_eps = copy(&targets);
+            for index in 0..ntargets {
+                targets_eps[[index, component]] += eps;
+            }
+
+            let mut green_value_eps = rlst::rlst_dynamic_array1!(c64, [ntargets]);
+            kernel.evaluate_st(
                 EvalType::Value,
                 sources.data(),
-                targets.data(),
+                targets_eps.data(),
                 charges.data(),
-                expected.data_mut(),
+                green_value_eps.data_mut(),
             );
 
             for target_index in 0..ntargets {
                 assert_relative_eq!(
-                    green_value[[target_index, charge_index]],
-                    expected[[target_index]],
-                    epsilon = 1E-12
+                    (green_value_eps[[target_index]] - green_value[[target_index]])
+                        * c64::from_real(1.0 / eps),
+                    expected[[1 + component, target_index]],
+                    epsilon = 1E-5
                 );
             }
         }
+    }
 
-        let mut green_value_deriv_t = rlst::rlst_dynamic_array2!(c64, [nsources, 4 * ntargets]);
+    #[test]
+    fn test_helmholtz_3d_one_source() {
+        let ntargets = 7;
 
-        Helmholtz3dKernel::<c64>::new(wavenumber).assemble_st(
-            EvalType::ValueDeriv,
-            sources.data(),
-            targets.data(),
-            green_value_deriv_t.data_mut(),
-        );
+        let mut sources = rlst::rlst_dynamic_array2!(f64, [1, 3]);
+        let mut targets = rlst::rlst_dynamic_array2!(f64, [ntargets, 3]);
+        let mut charges = rlst::rlst_dynamic_array1!(c64, [1]);
+
+        sources.fill_from_seed_equally_distributed(0);
+        targets.fill_from_seed_equally_distributed(1);
+        charges.fill_from_seed_equally_distributed(2);
 
-        // The matrix needs to be transposed so that the first row corresponds to the first target, etc.
+        for kernel in [
+            Helmholtz3dKernel::<c64>::new(1.5),
+            Helmholtz3dKernel::<c64>::new_complex(c64::complex(1.5, 0.3)),
+        ] {
+            for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+                let range_dim = helmholtz_component_count(eval_type);
 
-        let mut green_value_deriv = rlst::rlst_dynamic_array2!(c64, [4 * ntargets, nsources]);
-        green_value_deriv.fill_from(green_value_deriv_t.transpose());
+                let mut actual = rlst::rlst_dynamic_array2!(c64, [range_dim, ntargets]);
+                let mut expected = rlst::rlst_dynamic_array2!(c64, [range_dim, ntargets]);
 
-        for charge_index in 0..nsources {
-            let mut charges = rlst::rlst_dynamic_array1![c64, [nsources]];
-            let mut expected = rlst::rlst_dynamic_array2!(c64, [4, ntargets]);
+                kernel.evaluate_st(
+                    eval_type,
+                    sources.data(),
+                    targets.data(),
+                    charges.data(),
+                    actual.data_mut(),
+                );
 
-            charges[[charge_index]] = c64::complex(1.0, 0.0);
+                for (target_index, my_chunk) in
+                    expected.data_mut().chunks_exact_mut(range_dim).enumerate()
+                {
+                    let target = [
+                        targets[[target_index, 0]],
+                        targets[[target_index, 1]],
+                        targets[[target_index, 2]],
+                    ];
+                    evaluate_helmholtz_one_target_complex(
+                        eval_type,
+                        &target,
+                        sources.data(),
+                        charges.data(),
+                        kernel.complex_wavenumber(),
+                        my_chunk,
+                    );
+                }
 
-            Helmholtz3dKernel::<c64>::new(wavenumber).evaluate_st(
-                EvalType::ValueDeriv,
+                for target_index in 0..ntargets {
+                    for deriv_index in 0..range_dim {
+                        assert_relative_eq!(
+                            actual[[deriv_index, target_index]],
+                            expected[[deriv_index, target_index]],
+                            epsilon = 1E-12
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_helmholtz_3d_multi_query() {
+        let nsources = 5;
+        let ntargets = [3, 1, 4];
+
+        let mut sources = rlst::rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut charges = rlst::rlst_dynamic_array1!(c64, [nsources]);
+        let mut targets = ntargets.map(|n| rlst::rlst_dynamic_array2!(f64, [n, 3]));
+
+        sources.fill_from_seed_equally_distributed(0);
+        charges.fill_from_seed_equally_distributed(1);
+        for (seed, t) in targets.iter_mut().enumerate() {
+            t.fill_from_seed_equally_distributed(2 + seed);
+        }
+
+        for kernel in [
+            Helmholtz3dKernel::<c64>::new(1.5),
+            Helmholtz3dKernel::<c64>::new_complex(c64::complex(1.5, 0.7)),
+        ] {
+            for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+                let range_dim = helmholtz_component_count(eval_type);
+
+                let mut actual = ntargets.map(|n| vec![c64::default(); range_dim * n]);
+                let mut expected = ntargets.map(|n| vec![c64::default(); range_dim * n]);
+
+                let query_sets = targets.iter().map(|t| t.data()).collect::<Vec<_>>();
+                let mut outs = actual
+                    .iter_mut()
+                    .map(|a| a.as_mut_slice())
+                    .collect::<Vec<_>>();
+
+                kernel.evaluate_st_multi_query(
+                    eval_type,
+                    sources.data(),
+                    charges.data(),
+                    &query_sets,
+                    &mut outs,
+                );
+
+                for (t, e) in targets.iter().zip(expected.iter_mut()) {
+                    kernel.evaluate_st(eval_type, sources.data(), t.data(), charges.data(), e);
+                }
+
+                for (a, e) in actual.iter().zip(expected.iter()) {
+                    for (a_value, e_value) in a.iter().zip(e.iter()) {
+                        assert_relative_eq!(*a_value, *e_value, epsilon = 1E-12);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_helmholtz_3d_far_field_pattern() {
+        let radius: f64 = 1E5;
+
+        let nsources = 5;
+        let ndirections = 4;
+
+        let mut sources = rlst::rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut directions = rlst::rlst_dynamic_array2!(f64, [ndirections, 3]);
+        let mut charges = rlst::rlst_dynamic_array1!(c64, [nsources]);
+
+        sources.fill_from_seed_equally_distributed(0);
+        directions.fill_from_seed_equally_distributed(1);
+        charges.fill_from_seed_equally_distributed(2);
+
+        let mut targets = rlst::rlst_dynamic_array2!(f64, [ndirections, 3]);
+
+        for index in 0..ndirections {
+            let norm = (directions[[index, 0]].square()
+                + directions[[index, 1]].square()
+                + directions[[index, 2]].square())
+            .sqrt();
+            for component in 0..3 {
+                directions[[index, component]] /= norm;
+                targets[[index, component]] = radius * directions[[index, component]];
+            }
+        }
+
+        // The far field expansion also holds for a lossy wavenumber, as long as the decay
+        // over the radius stays representable.
+        for wavenumber in [c64::complex(1.5, 0.0), c64::complex(1.5, 1E-3)] {
+            let kernel = Helmholtz3dKernel::<c64>::new_complex(wavenumber);
+
+            let mut pattern = rlst::rlst_dynamic_array1!(c64, [ndirections]);
+            let mut far_values = rlst::rlst_dynamic_array1!(c64, [ndirections]);
+
+            kernel.far_field_pattern(
+                sources.data(),
+                charges.data(),
+                directions.data(),
+                pattern.data_mut(),
+            );
+
+            kernel.evaluate_st(
+                EvalType::Value,
                 sources.data(),
                 targets.data(),
                 charges.data(),
-                expected.data_mut(),
+                far_values.data_mut(),
             );
 
-            for deriv_index in 0..4 {
+            for index in 0..ndirections {
+                let expected = far_values[[index]]
+                    * c64::exp(c64::complex(0.0, -radius) * wavenumber)
+                    * radius;
+
+                assert_relative_eq!(pattern[[index]], expected, epsilon = 1E-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_assemble_helmholtz_3d() {
+        let nsources = 3;
+        let ntargets = 5;
+        let wavenumber: f64 = 1.5;
+
+        let mut sources = rlst::rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut targets = rlst::rlst_dynamic_array2!(f64, [ntargets, 3]);
+
+        sources.fill_from_seed_equally_distributed(1);
+        targets.fill_from_seed_equally_distributed(2);
+
+        for kernel in [
+            Helmholtz3dKernel::<c64>::new(wavenumber),
+            Helmholtz3dKernel::<c64>::new_complex(c64::complex(wavenumber, 0.7)),
+        ] {
+            let mut green_value_t = rlst::rlst_dynamic_array2!(c64, [nsources, ntargets]);
+
+            kernel.assemble_st(
+                EvalType::Value,
+                sources.data(),
+                targets.data(),
+                green_value_t.data_mut(),
+            );
+
+            // The matrix needs to be transposed so that the first row corresponds to the first target,
+            // second row to the second target and so on.
+
+            let mut green_value = rlst::rlst_dynamic_array2!(c64, [ntargets, nsources]);
+            green_value.fill_from(green_value_t.transpose());
+
+            for charge_index in 0..nsources {
+                let mut charges = rlst::rlst_dynamic_array1![c64, [nsources]];
+                let mut expected = rlst::rlst_dynamic_array1![c64, [ntargets]];
+                charges[[charge_index]] = c64::complex(1.0, 0.0);
+
+                kernel.evaluate_st(
+                    EvalType::Value,
+                    sources.data(),
+                    targets.data(),
+                    charges.data(),
+                    expected.data_mut(),
+                );
+
                 for target_index in 0..ntargets {
                     assert_relative_eq!(
-                        green_value_deriv[[4 * target_index + deriv_index, charge_index]],
-                        expected[[deriv_index, target_index]],
+                        green_value[[target_index, charge_index]],
+                        expected[[target_index]],
                         epsilon = 1E-12
                     );
                 }
             }
+
+            let mut green_value_deriv_t = rlst::rlst_dynamic_array2!(c64, [nsources, 4 * ntargets]);
+
+            kernel.assemble_st(
+                EvalType::ValueDeriv,
+                sources.data(),
+                targets.data(),
+                green_value_deriv_t.data_mut(),
+            );
+
+            // The matrix needs to be transposed so that the first row corresponds to the first target, etc.
+
+            let mut green_value_deriv = rlst::rlst_dynamic_array2!(c64, [4 * ntargets, nsources]);
+            green_value_deriv.fill_from(green_value_deriv_t.transpose());
+
+            for charge_index in 0..nsources {
+                let mut charges = rlst::rlst_dynamic_array1![c64, [nsources]];
+                let mut expected = rlst::rlst_dynamic_array2!(c64, [4, ntargets]);
+
+                charges[[charge_index]] = c64::complex(1.0, 0.0);
+
+                kernel.evaluate_st(
+                    EvalType::ValueDeriv,
+                    sources.data(),
+                    targets.data(),
+                    charges.data(),
+                    expected.data_mut(),
+                );
+
+                for deriv_index in 0..4 {
+                    for target_index in 0..ntargets {
+                        assert_relative_eq!(
+                            green_value_deriv[[4 * target_index + deriv_index, charge_index]],
+                            expected[[deriv_index, target_index]],
+                            epsilon = 1E-12
+                        );
+                    }
+                }
+            }
         }
     }
 
     #[test]
-    fn test_assemble_diag_helmholtz_3d() {
-        let nsources = 5;
+    fn test_assemble_scaled_helmholtz_3d() {
+        let nsources = 3;
         let ntargets = 5;
-        let wavenumber: f64 = 1.5;
 
         let mut sources = rlst::rlst_dynamic_array2!(f64, [nsources, 3]);
         let mut targets = rlst::rlst_dynamic_array2!(f64, [ntargets, 3]);
+        let mut scales = rlst::rlst_dynamic_array1!(c64, [ntargets]);
 
         sources.fill_from_seed_equally_distributed(1);
         targets.fill_from_seed_equally_distributed(2);
+        scales.fill_from_seed_equally_distributed(3);
 
-        let mut green_value_diag = rlst::rlst_dynamic_array1!(c64, [ntargets]);
-        let mut green_value_diag_deriv = rlst::rlst_dynamic_array2!(c64, [4, ntargets]);
+        let kernel = Helmholtz3dKernel::<c64>::new_complex(c64::complex(1.5, 0.7));
 
-        Helmholtz3dKernel::<c64>::new(wavenumber).assemble_diagonal_st(
-            EvalType::Value,
-            sources.data(),
-            targets.data(),
-            green_value_diag.data_mut(),
-        );
-        Helmholtz3dKernel::<c64>::new(wavenumber).assemble_diagonal_st(
-            EvalType::ValueDeriv,
-            sources.data(),
-            targets.data(),
-            green_value_diag_deriv.data_mut(),
-        );
+        for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+            let range_dim = helmholtz_component_count(eval_type);
 
-        let mut green_value_t = rlst::rlst_dynamic_array2!(c64, [nsources, ntargets]);
-
-        Helmholtz3dKernel::<c64>::new(wavenumber).assemble_st(
-            EvalType::Value,
-            sources.data(),
-            targets.data(),
-            green_value_t.data_mut(),
-        );
+            let mut expected = rlst::rlst_dynamic_array2!(c64, [range_dim * nsources, ntargets]);
+            let mut actual = rlst::rlst_dynamic_array2!(c64, [range_dim * nsources, ntargets]);
 
-        // The matrix needs to be transposed so that the first row corresponds to the first target,
-        // second row to the second target and so on.
+            kernel.assemble_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                expected.data_mut(),
+            );
 
-        let mut green_value = rlst::rlst_dynamic_array2!(c64, [ntargets, nsources]);
-        green_value.fill_from(green_value_t.transpose());
+            kernel.assemble_st_scaled(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                scales.data(),
+                actual.data_mut(),
+            );
 
-        let mut green_value_deriv_t = rlst::rlst_dynamic_array2!(c64, [nsources, 4 * ntargets]);
+            for target_index in 0..ntargets {
+                for row in 0..range_dim * nsources {
+                    assert_relative_eq!(
+                        actual[[row, target_index]],
+                        expected[[row, target_index]] * scales[[target_index]],
+                        epsilon = 1E-12
+                    );
+                }
+            }
+        }
+    }
 
-        Helmholtz3dKernel::<c64>::new(wavenumber).assemble_st(
-            EvalType::ValueDeriv,
-            sources.data(),
-            targets.data(),
-            green_value_deriv_t.data_mut(),
-        );
+    #[test]
+    fn test_assemble_symmetric_helmholtz_3d() {
+        let npoints = 7;
 
-        // The matrix needs to be transposed so that the first row corresponds to the first target, etc.
+        let mut points = rlst::rlst_dynamic_array2!(f64, [npoints, 3]);
+        points.fill_from_seed_equally_distributed(1);
 
-        let mut green_value_deriv = rlst::rlst_dynamic_array2!(c64, [4 * ntargets, nsources]);
-        green_value_deriv.fill_from(green_value_deriv_t.transpose());
+        for kernel in [
+            Helmholtz3dKernel::<c64>::new(1.5),
+            Helmholtz3dKernel::<c64>::new_complex(c64::complex(1.5, 0.7)),
+        ] {
+            let mut expected = rlst::rlst_dynamic_array2!(c64, [npoints, npoints]);
+            let mut actual = rlst::rlst_dynamic_array2!(c64, [npoints, npoints]);
 
-        for index in 0..nsources {
-            assert_relative_eq!(
-                green_value[[index, index]],
-                green_value_diag[[index]],
-                epsilon = 1E-12
+            kernel.assemble_st(
+                EvalType::Value,
+                points.data(),
+                points.data(),
+                expected.data_mut(),
             );
 
-            assert_relative_eq!(
-                green_value_deriv[[4 * index, index]],
-                green_value_diag_deriv[[0, index]],
-                epsilon = 1E-12,
-            );
+            kernel.assemble_symmetric_st(points.data(), actual.data_mut());
 
-            assert_relative_eq!(
-                green_value_deriv[[4 * index + 1, index]],
-                green_value_diag_deriv[[1, index]],
-                epsilon = 1E-12,
+            for row in 0..npoints {
+                for col in 0..npoints {
+                    assert_relative_eq!(actual[[row, col]], expected[[row, col]], epsilon = 1E-14);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_assemble_diag_helmholtz_3d() {
+        let nsources = 5;
+        let ntargets = 5;
+        let wavenumber: f64 = 1.5;
+
+        let mut sources = rlst::rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut targets = rlst::rlst_dynamic_array2!(f64, [ntargets, 3]);
+
+        sources.fill_from_seed_equally_distributed(1);
+        targets.fill_from_seed_equally_distributed(2);
+
+        for kernel in [
+            Helmholtz3dKernel::<c64>::new(wavenumber),
+            Helmholtz3dKernel::<c64>::new_complex(c64::complex(wavenumber, 0.7)),
+        ] {
+            let mut green_value_diag = rlst::rlst_dynamic_array1!(c64, [ntargets]);
+            let mut green_value_diag_deriv = rlst::rlst_dynamic_array2!(c64, [4, ntargets]);
+
+            kernel.assemble_diagonal_st(
+                EvalType::Value,
+                sources.data(),
+                targets.data(),
+                green_value_diag.data_mut(),
+            );
+            kernel.assemble_diagonal_st(
+                EvalType::ValueDeriv,
+                sources.data(),
+                targets.data(),
+                green_value_diag_deriv.data_mut(),
             );
 
-            assert_relative_eq!(
-                green_value_deriv[[4 * index + 2, index]],
-                green_value_diag_deriv[[2, index]],
-                epsilon = 1E-12,
+            let mut green_value_t = rlst::rlst_dynamic_array2!(c64, [nsources, ntargets]);
+
+            kernel.assemble_st(
+                EvalType::Value,
+                sources.data(),
+                targets.data(),
+                green_value_t.data_mut(),
             );
 
-            assert_relative_eq!(
-                green_value_deriv[[4 * index + 3, index]],
-                green_value_diag_deriv[[3, index]],
-                epsilon = 1E-12,
+            // The matrix needs to be transposed so that the first row corresponds to the first target,
+            // second row to the second target and so on.
+
+            let mut green_value = rlst::rlst_dynamic_array2!(c64, [ntargets, nsources]);
+            green_value.fill_from(green_value_t.transpose());
+
+            let mut green_value_deriv_t = rlst::rlst_dynamic_array2!(c64, [nsources, 4 * ntargets]);
+
+            kernel.assemble_st(
+                EvalType::ValueDeriv,
+                sources.data(),
+                targets.data(),
+                green_value_deriv_t.data_mut(),
             );
+
+            // The matrix needs to be transposed so that the first row corresponds to the first target, etc.
+
+            let mut green_value_deriv = rlst::rlst_dynamic_array2!(c64, [4 * ntargets, nsources]);
+            green_value_deriv.fill_from(green_value_deriv_t.transpose());
+
+            for index in 0..nsources {
+                assert_relative_eq!(
+                    green_value[[index, index]],
+                    green_value_diag[[index]],
+                    epsilon = 1E-12
+                );
+
+                assert_relative_eq!(
+                    green_value_deriv[[4 * index, index]],
+                    green_value_diag_deriv[[0, index]],
+                    epsilon = 1E-12,
+                );
+
+                assert_relative_eq!(
+                    green_value_deriv[[4 * index + 1, index]],
+                    green_value_diag_deriv[[1, index]],
+                    epsilon = 1E-12,
+                );
+
+                assert_relative_eq!(
+                    green_value_deriv[[4 * index + 2, index]],
+                    green_value_diag_deriv[[2, index]],
+                    epsilon = 1E-12,
+                );
+
+                assert_relative_eq!(
+                    green_value_deriv[[4 * index + 3, index]],
+                    green_value_diag_deriv[[3, index]],
+                    epsilon = 1E-12,
+                );
+            }
         }
     }
 }
@@ -79,6 +79,27 @@ pub(crate) fn check_dimensions_assemble<K: Kernel, T: RlstScalar>(
     );
 }
 
+pub(crate) fn check_dimensions_assemble_scaled<K: Kernel, T: RlstScalar>(
+    kernel: &K,
+    eval_type: EvalType,
+    sources: &[T::Real],
+    targets: &[T::Real],
+    target_scales: &[T],
+    result: &[T],
+) {
+    check_dimensions_assemble(kernel, eval_type, sources, targets, result);
+
+    let ntargets = targets.len() / kernel.space_dimension();
+
+    assert_eq!(
+        target_scales.len(),
+        ntargets,
+        "Wrong dimension for `target_scales`. {} != {} ",
+        target_scales.len(),
+        ntargets,
+    );
+}
+
 pub(crate) fn check_dimensions_assemble_diagonal<K: Kernel, T: RlstScalar>(
     kernel: &K,
     eval_type: EvalType,
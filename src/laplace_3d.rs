@@ -1,6 +1,7 @@
 //! Implementation of the Laplace kernel
 use crate::helpers::{
-    check_dimensions_assemble, check_dimensions_assemble_diagonal, check_dimensions_evaluate,
+    check_dimensions_assemble, check_dimensions_assemble_diagonal,
+    check_dimensions_assemble_scaled, check_dimensions_evaluate,
 };
 use crate::traits::Kernel;
 use crate::types::EvalType;
@@ -24,6 +25,162 @@ impl<T: RlstScalar> Laplace3dKernel<T> {
     }
 }
 
+impl<T: RlstScalar + Send + Sync> Laplace3dKernel<T>
+where
+    <T as RlstScalar>::Real: Send + Sync,
+{
+    /// Single threaded assembly of the adjoint double layer kernel matrix.
+    ///
+    /// The adjoint double layer kernel is the derivative of the single layer kernel with
+    /// respect to the normal at the target, that is `n(x) . grad_x G(x, y)` for a target `x`
+    /// and a source `y`. It is obtained by contracting the derivative components of
+    /// [EvalType::ValueDeriv] with the target normals.
+    ///
+    /// - `sources`: The source points in the same layout as for [Kernel::assemble_st].
+    /// - `targets`: The target points in the same layout as for [Kernel::assemble_st].
+    /// - `target_normals`: The normals at the targets. The memory layout is the same as for targets.
+    /// - `result`: The result array. It has the same layout as for [Kernel::assemble_st] with [EvalType::Value].
+    pub fn assemble_adjoint_double_layer_st(
+        &self,
+        sources: &[<T as RlstScalar>::Real],
+        targets: &[<T as RlstScalar>::Real],
+        target_normals: &[<T as RlstScalar>::Real],
+        result: &mut [T],
+    ) {
+        check_dimensions_assemble(self, EvalType::Value, sources, targets, result);
+        assert_eq!(
+            target_normals.len(),
+            targets.len(),
+            "Wrong dimension for `target_normals`. {} != {} ",
+            target_normals.len(),
+            targets.len(),
+        );
+        let ntargets = targets.len() / self.space_dimension();
+        let nsources = sources.len() / self.space_dimension();
+        let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+        let zero_real = <T::Real as num::Zero>::zero();
+        let one_real = <T::Real as num::One>::one();
+
+        let sources0 = &sources[0..nsources];
+        let sources1 = &sources[nsources..2 * nsources];
+        let sources2 = &sources[2 * nsources..3 * nsources];
+
+        result
+            .chunks_exact_mut(nsources)
+            .enumerate()
+            .for_each(|(target_index, my_chunk)| {
+                let target = [
+                    targets[target_index],
+                    targets[ntargets + target_index],
+                    targets[2 * ntargets + target_index],
+                ];
+                let normal = [
+                    target_normals[target_index],
+                    target_normals[ntargets + target_index],
+                    target_normals[2 * ntargets + target_index],
+                ];
+
+                for index in 0..nsources {
+                    let diff0 = sources0[index] - target[0];
+                    let diff1 = sources1[index] - target[1];
+                    let diff2 = sources2[index] - target[2];
+                    let diff_norm = (diff0 * diff0 + diff1 * diff1 + diff2 * diff2).sqrt();
+                    let inv_diff_norm = {
+                        if diff_norm == zero_real {
+                            zero_real
+                        } else {
+                            one_real / diff_norm
+                        }
+                    };
+                    let inv_diff_norm_cubed = inv_diff_norm * inv_diff_norm * inv_diff_norm;
+
+                    my_chunk[index] = T::from_real(
+                        (diff0 * normal[0] + diff1 * normal[1] + diff2 * normal[2])
+                            * inv_diff_norm_cubed
+                            * m_inv_4pi,
+                    );
+                }
+            });
+    }
+
+    /// Single threaded evaluation of potentials that also returns the pairwise distances.
+    ///
+    /// The potentials are computed as in [Kernel::evaluate_st] with [EvalType::Value]. In addition
+    /// `out_distance` is filled with the distances between all targets and sources, computed
+    /// in the same pass so that they do not need to be recomputed by the caller.
+    ///
+    /// - `sources`: The source points in the same layout as for [Kernel::evaluate_st].
+    /// - `targets`: The target points in the same layout as for [Kernel::evaluate_st].
+    /// - `charges`: The charges, one for each source point.
+    /// - `out_potential`: The potential at each target, as for [Kernel::evaluate_st].
+    /// - `out_distance`: The distances. The entry `nsources * i + j` is the distance of source `j`
+    ///   from target `i`.
+    pub fn evaluate_with_distance(
+        &self,
+        sources: &[<T as RlstScalar>::Real],
+        targets: &[<T as RlstScalar>::Real],
+        charges: &[T],
+        out_potential: &mut [T],
+        out_distance: &mut [<T as RlstScalar>::Real],
+    ) {
+        check_dimensions_evaluate(
+            self,
+            EvalType::Value,
+            sources,
+            targets,
+            charges,
+            out_potential,
+        );
+        let ntargets = targets.len() / self.space_dimension();
+        let nsources = sources.len() / self.space_dimension();
+        assert_eq!(
+            out_distance.len(),
+            nsources * ntargets,
+            "Wrong dimension for `out_distance`. {} != {} ",
+            out_distance.len(),
+            nsources * ntargets,
+        );
+        let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+        let zero_real = <T::Real as num::Zero>::zero();
+        let one_real = <T::Real as num::One>::one();
+
+        let sources0 = &sources[0..nsources];
+        let sources1 = &sources[nsources..2 * nsources];
+        let sources2 = &sources[2 * nsources..3 * nsources];
+
+        out_potential
+            .iter_mut()
+            .zip(out_distance.chunks_exact_mut(nsources))
+            .enumerate()
+            .for_each(|(target_index, (potential, distances))| {
+                let target = [
+                    targets[target_index],
+                    targets[ntargets + target_index],
+                    targets[2 * ntargets + target_index],
+                ];
+
+                let mut my_result = T::zero();
+                for index in 0..nsources {
+                    let diff0 = sources0[index] - target[0];
+                    let diff1 = sources1[index] - target[1];
+                    let diff2 = sources2[index] - target[2];
+                    let diff_norm = (diff0 * diff0 + diff1 * diff1 + diff2 * diff2).sqrt();
+                    let inv_diff_norm = {
+                        if diff_norm == zero_real {
+                            zero_real
+                        } else {
+                            one_real / diff_norm
+                        }
+                    };
+
+                    distances[index] = diff_norm;
+                    my_result += charges[index].mul_real(inv_diff_norm);
+                }
+                *potential += my_result.mul_real(m_inv_4pi);
+            });
+    }
+}
+
 impl<T: RlstScalar + Send + Sync> Kernel for Laplace3dKernel<T>
 where
     <T as RlstScalar>::Real: Send + Sync,
@@ -50,6 +207,11 @@ where
         let ntargets = targets.len() / self.space_dimension();
         let range_dim = self.range_component_count(eval_type);
 
+        if charges.len() == 1 {
+            evaluate_laplace_one_source(eval_type, sources, targets, charges[0], result);
+            return;
+        }
+
         result
             .chunks_exact_mut(range_dim)
             .enumerate()
@@ -64,6 +226,66 @@ where
             });
     }
 
+    fn evaluate_st_multi_query(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        charges: &[Self::T],
+        query_sets: &[&[<Self::T as RlstScalar>::Real]],
+        outs: &mut [&mut [Self::T]],
+    ) {
+        assert_eq!(
+            query_sets.len(),
+            outs.len(),
+            "Number of query sets and outputs do not match. {} != {} ",
+            query_sets.len(),
+            outs.len(),
+        );
+        for (targets, result) in query_sets.iter().zip(outs.iter()) {
+            check_dimensions_evaluate(self, eval_type, sources, targets, charges, result);
+        }
+
+        let nsources = charges.len();
+        let range_dim = self.range_component_count(eval_type);
+        let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+        let one_real = <T::Real as num::One>::one();
+
+        // Split the sources and scale the charges once for all query sets.
+        let split_sources = [
+            &sources[0..nsources],
+            &sources[nsources..2 * nsources],
+            &sources[2 * nsources..3 * nsources],
+        ];
+        let scaled_charges = charges
+            .iter()
+            .map(|charge| charge.mul_real(m_inv_4pi))
+            .collect::<Vec<_>>();
+
+        for (targets, result) in query_sets.iter().zip(outs.iter_mut()) {
+            let ntargets = targets.len() / self.space_dimension();
+
+            result
+                .chunks_exact_mut(range_dim)
+                .enumerate()
+                .for_each(|(target_index, my_chunk)| {
+                    let target = [
+                        targets[target_index],
+                        targets[ntargets + target_index],
+                        targets[2 * ntargets + target_index],
+                    ];
+
+                    evaluate_laplace_one_target_impl(
+                        eval_type,
+                        &target,
+                        split_sources,
+                        &scaled_charges,
+                        one_real,
+                        my_chunk,
+                    )
+                });
+        }
+    }
+
     fn evaluate_mt(
         &self,
         eval_type: EvalType,
@@ -116,6 +338,72 @@ where
             });
     }
 
+    fn assemble_st_scaled(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        targets: &[<Self::T as RlstScalar>::Real],
+        target_scales: &[Self::T],
+        result: &mut [Self::T],
+    ) {
+        check_dimensions_assemble_scaled(self, eval_type, sources, targets, target_scales, result);
+        let ntargets = targets.len() / self.space_dimension();
+        let nsources = sources.len() / self.space_dimension();
+        let range_dim = self.range_component_count(eval_type);
+
+        result
+            .chunks_exact_mut(range_dim * nsources)
+            .zip(target_scales)
+            .enumerate()
+            .for_each(|(target_index, (my_chunk, scale))| {
+                let target = [
+                    targets[target_index],
+                    targets[ntargets + target_index],
+                    targets[2 * ntargets + target_index],
+                ];
+
+                assemble_laplace_one_target(eval_type, &target, sources, my_chunk);
+
+                my_chunk.iter_mut().for_each(|value| *value *= *scale);
+            });
+    }
+
+    fn assemble_symmetric_st(
+        &self,
+        points: &[<Self::T as RlstScalar>::Real],
+        result: &mut [Self::T],
+    ) {
+        check_dimensions_assemble(self, EvalType::Value, points, points, result);
+        let npoints = points.len() / self.space_dimension();
+        let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+        let zero_real = <T::Real as num::Zero>::zero();
+        let one_real = <T::Real as num::One>::one();
+
+        let points0 = &points[0..npoints];
+        let points1 = &points[npoints..2 * npoints];
+        let points2 = &points[2 * npoints..3 * npoints];
+
+        for target_index in 0..npoints {
+            for source_index in target_index..npoints {
+                let diff0 = points0[source_index] - points0[target_index];
+                let diff1 = points1[source_index] - points1[target_index];
+                let diff2 = points2[source_index] - points2[target_index];
+                let diff_norm = (diff0 * diff0 + diff1 * diff1 + diff2 * diff2).sqrt();
+                let inv_diff_norm = {
+                    if diff_norm == zero_real {
+                        zero_real
+                    } else {
+                        one_real / diff_norm
+                    }
+                };
+
+                let value = T::from_real(inv_diff_norm * m_inv_4pi);
+                result[target_index * npoints + source_index] = value;
+                result[source_index * npoints + target_index] = value;
+            }
+        }
+    }
+
     fn assemble_mt(
         &self,
         eval_type: EvalType,
@@ -219,15 +507,39 @@ pub fn evaluate_laplace_one_target<T: RlstScalar>(
     charges: &[T],
     result: &mut [T],
 ) {
-    let ncharges = charges.len();
-    let nsources = ncharges;
+    let nsources = charges.len();
     let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+
+    evaluate_laplace_one_target_impl(
+        eval_type,
+        target,
+        [
+            &sources[0..nsources],
+            &sources[nsources..2 * nsources],
+            &sources[2 * nsources..3 * nsources],
+        ],
+        charges,
+        m_inv_4pi,
+        result,
+    )
+}
+
+/// Evaluate laplace kernel with one target for sources split into their components.
+///
+/// The sums over the sources are multiplied by `scale` before they are added to `result`.
+fn evaluate_laplace_one_target_impl<T: RlstScalar>(
+    eval_type: EvalType,
+    target: &[<T as RlstScalar>::Real],
+    sources: [&[<T as RlstScalar>::Real]; 3],
+    charges: &[T],
+    scale: T::Real,
+    result: &mut [T],
+) {
+    let nsources = charges.len();
     let zero_real = <T::Real as num::Zero>::zero();
     let one_real = <T::Real as num::One>::one();
 
-    let sources0 = &sources[0..nsources];
-    let sources1 = &sources[nsources..2 * nsources];
-    let sources2 = &sources[2 * nsources..3 * nsources];
+    let [sources0, sources1, sources2] = sources;
 
     let mut diff0: T::Real;
     let mut diff1: T::Real;
@@ -251,7 +563,7 @@ pub fn evaluate_laplace_one_target<T: RlstScalar>(
 
                 my_result += charges[index].mul_real(inv_diff_norm);
             }
-            result[0] += my_result.mul_real(m_inv_4pi);
+            result[0] += my_result.mul_real(scale);
         }
         EvalType::ValueDeriv => {
             // Cannot simply use an array my_result as this is not
@@ -282,10 +594,80 @@ pub fn evaluate_laplace_one_target<T: RlstScalar>(
                 my_result3 += charges[index].mul_real(diff2 * inv_diff_norm_cubed);
             }
 
-            result[0] += my_result0.mul_real(m_inv_4pi);
-            result[1] += my_result1.mul_real(m_inv_4pi);
-            result[2] += my_result2.mul_real(m_inv_4pi);
-            result[3] += my_result3.mul_real(m_inv_4pi);
+            result[0] += my_result0.mul_real(scale);
+            result[1] += my_result1.mul_real(scale);
+            result[2] += my_result2.mul_real(scale);
+            result[3] += my_result3.mul_real(scale);
+        }
+    }
+}
+
+/// Evaluate Laplace kernel with one source
+///
+/// This is the counterpart of [evaluate_laplace_one_target] for a single source and many targets.
+/// The targets are given in the same layout as for [Kernel::evaluate_st].
+pub fn evaluate_laplace_one_source<T: RlstScalar>(
+    eval_type: EvalType,
+    source: &[<T as RlstScalar>::Real],
+    targets: &[<T as RlstScalar>::Real],
+    charge: T,
+    result: &mut [T],
+) {
+    assert_eq!(source.len(), 3);
+    assert_eq!(targets.len() % 3, 0);
+    let ntargets = targets.len() / 3;
+    let m_inv_4pi = num::cast::<f64, T::Real>(0.25 * f64::FRAC_1_PI()).unwrap();
+    let zero_real = <T::Real as num::Zero>::zero();
+    let one_real = <T::Real as num::One>::one();
+
+    let targets0 = &targets[0..ntargets];
+    let targets1 = &targets[ntargets..2 * ntargets];
+    let targets2 = &targets[2 * ntargets..3 * ntargets];
+
+    let charge = charge.mul_real(m_inv_4pi);
+
+    let mut diff0: T::Real;
+    let mut diff1: T::Real;
+    let mut diff2: T::Real;
+
+    match eval_type {
+        EvalType::Value => {
+            for (index, my_result) in result.iter_mut().enumerate() {
+                diff0 = source[0] - targets0[index];
+                diff1 = source[1] - targets1[index];
+                diff2 = source[2] - targets2[index];
+                let diff_norm = (diff0 * diff0 + diff1 * diff1 + diff2 * diff2).sqrt();
+                let inv_diff_norm = {
+                    if diff_norm == zero_real {
+                        zero_real
+                    } else {
+                        one_real / diff_norm
+                    }
+                };
+
+                *my_result += charge.mul_real(inv_diff_norm);
+            }
+        }
+        EvalType::ValueDeriv => {
+            for (index, my_result) in result.chunks_exact_mut(4).enumerate() {
+                diff0 = source[0] - targets0[index];
+                diff1 = source[1] - targets1[index];
+                diff2 = source[2] - targets2[index];
+                let diff_norm = (diff0 * diff0 + diff1 * diff1 + diff2 * diff2).sqrt();
+                let inv_diff_norm = {
+                    if diff_norm == zero_real {
+                        zero_real
+                    } else {
+                        one_real / diff_norm
+                    }
+                };
+                let inv_diff_norm_cubed = inv_diff_norm * inv_diff_norm * inv_diff_norm;
+
+                my_result[0] += charge.mul_real(inv_diff_norm);
+                my_result[1] += charge.mul_real(diff0 * inv_diff_norm_cubed);
+                my_result[2] += charge.mul_real(diff1 * inv_diff_norm_cubed);
+                my_result[3] += charge.mul_real(diff2 * inv_diff_norm_cubed);
+            }
         }
     }
 }
@@ -547,6 +929,160 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_evaluate_with_distance_laplace_3d() {
+        let nsources = 5;
+        let ntargets = 3;
+
+        let sources = rand_mat([nsources, 3]);
+        let targets = rand_mat([ntargets, 3]);
+        let charges = rand_vec(nsources);
+        let mut expected = rlst_dynamic_array2!(f64, [ntargets, 1]);
+        let mut potential = rlst_dynamic_array2!(f64, [ntargets, 1]);
+        let mut distance = rlst_dynamic_array2!(f64, [nsources, ntargets]);
+
+        Laplace3dKernel::<f64>::default().evaluate_st(
+            EvalType::Value,
+            sources.data(),
+            targets.data(),
+            charges.data(),
+            expected.data_mut(),
+        );
+
+        Laplace3dKernel::<f64>::default().evaluate_with_distance(
+            sources.data(),
+            targets.data(),
+            charges.data(),
+            potential.data_mut(),
+            distance.data_mut(),
+        );
+
+        for target_index in 0..ntargets {
+            assert_relative_eq!(
+                potential[[target_index, 0]],
+                expected[[target_index, 0]],
+                epsilon = 1E-14
+            );
+
+            for source_index in 0..nsources {
+                let dist = ((targets[[target_index, 0]] - sources[[source_index, 0]]).square()
+                    + (targets[[target_index, 1]] - sources[[source_index, 1]]).square()
+                    + (targets[[target_index, 2]] - sources[[source_index, 2]]).square())
+                .sqrt();
+
+                assert_relative_eq!(
+                    distance[[source_index, target_index]],
+                    dist,
+                    epsilon = 1E-14
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_laplace_3d_one_source() {
+        let ntargets = 7;
+
+        let sources = rand_mat([1, 3]);
+        let targets = rand_mat([ntargets, 3]);
+        let charges = rand_vec(1);
+
+        for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+            let range_dim = laplace_component_count(eval_type);
+
+            let mut actual = rlst_dynamic_array2!(f64, [range_dim, ntargets]);
+            let mut expected = rlst_dynamic_array2!(f64, [range_dim, ntargets]);
+
+            Laplace3dKernel::<f64>::default().evaluate_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                charges.data(),
+                actual.data_mut(),
+            );
+
+            for (target_index, my_chunk) in
+                expected.data_mut().chunks_exact_mut(range_dim).enumerate()
+            {
+                let target = [
+                    targets[[target_index, 0]],
+                    targets[[target_index, 1]],
+                    targets[[target_index, 2]],
+                ];
+                evaluate_laplace_one_target(
+                    eval_type,
+                    &target,
+                    sources.data(),
+                    charges.data(),
+                    my_chunk,
+                );
+            }
+
+            for target_index in 0..ntargets {
+                for deriv_index in 0..range_dim {
+                    assert_relative_eq!(
+                        actual[[deriv_index, target_index]],
+                        expected[[deriv_index, target_index]],
+                        epsilon = 1E-12
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_laplace_3d_multi_query() {
+        let nsources = 5;
+        let ntargets = [3, 1, 4];
+
+        let mut sources = rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut charges = rlst_dynamic_array1!(f64, [nsources]);
+        let mut targets = ntargets.map(|n| rlst_dynamic_array2!(f64, [n, 3]));
+
+        sources.fill_from_seed_equally_distributed(0);
+        charges.fill_from_seed_equally_distributed(1);
+        for (seed, t) in targets.iter_mut().enumerate() {
+            t.fill_from_seed_equally_distributed(2 + seed);
+        }
+
+        for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+            let range_dim = laplace_component_count(eval_type);
+
+            let mut actual = ntargets.map(|n| vec![0.0; range_dim * n]);
+            let mut expected = ntargets.map(|n| vec![0.0; range_dim * n]);
+
+            let query_sets = targets.iter().map(|t| t.data()).collect::<Vec<_>>();
+            let mut outs = actual
+                .iter_mut()
+                .map(|a| a.as_mut_slice())
+                .collect::<Vec<_>>();
+
+            Laplace3dKernel::<f64>::default().evaluate_st_multi_query(
+                eval_type,
+                sources.data(),
+                charges.data(),
+                &query_sets,
+                &mut outs,
+            );
+
+            for (t, e) in targets.iter().zip(expected.iter_mut()) {
+                Laplace3dKernel::<f64>::default().evaluate_st(
+                    eval_type,
+                    sources.data(),
+                    t.data(),
+                    charges.data(),
+                    e,
+                );
+            }
+
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                for (a_value, e_value) in a.iter().zip(e.iter()) {
+                    assert_relative_eq!(*a_value, *e_value, epsilon = 1E-14);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_assemble_laplace_3d() {
         let nsources = 3;
@@ -631,6 +1167,48 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_assemble_scaled_laplace_3d() {
+        let nsources = 3;
+        let ntargets = 5;
+
+        let sources = rand_mat([nsources, 3]);
+        let targets = rand_mat([ntargets, 3]);
+        let scales = rand_vec(ntargets);
+
+        for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+            let range_dim = laplace_component_count(eval_type);
+
+            let mut expected = rlst_dynamic_array2!(f64, [range_dim * nsources, ntargets]);
+            let mut actual = rlst_dynamic_array2!(f64, [range_dim * nsources, ntargets]);
+
+            Laplace3dKernel::<f64>::default().assemble_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                expected.data_mut(),
+            );
+
+            Laplace3dKernel::<f64>::default().assemble_st_scaled(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                scales.data(),
+                actual.data_mut(),
+            );
+
+            for target_index in 0..ntargets {
+                for row in 0..range_dim * nsources {
+                    assert_relative_eq!(
+                        actual[[row, target_index]],
+                        expected[[row, target_index]] * scales[[target_index, 0]],
+                        epsilon = 1E-12
+                    );
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_compare_assemble_with_direct_computation() {
         let nsources = 3;
@@ -648,6 +1226,157 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_assemble_surface_to_surface_laplace_3d() {
+        let nsources = 4;
+        let ntargets = 6;
+
+        let mut sources = rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut targets = rlst_dynamic_array2!(f64, [ntargets, 3]);
+        let mut charges = rlst_dynamic_array1!(f64, [nsources]);
+
+        sources.fill_from_seed_equally_distributed(1);
+        targets.fill_from_seed_equally_distributed(2);
+        charges.fill_from_seed_equally_distributed(3);
+
+        let kernel = Laplace3dKernel::<f64>::default();
+
+        for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+            let range_dim = kernel.range_component_count(eval_type);
+
+            let matrix =
+                kernel.assemble_surface_to_surface(eval_type, sources.data(), targets.data());
+
+            assert_eq!(matrix.shape(), [range_dim * ntargets, nsources]);
+
+            let mut expected = vec![0.0; range_dim];
+            for target_index in 0..ntargets {
+                for source_index in 0..nsources {
+                    kernel.greens_fct(
+                        eval_type,
+                        &[
+                            sources[[source_index, 0]],
+                            sources[[source_index, 1]],
+                            sources[[source_index, 2]],
+                        ],
+                        &[
+                            targets[[target_index, 0]],
+                            targets[[target_index, 1]],
+                            targets[[target_index, 2]],
+                        ],
+                        &mut expected,
+                    );
+
+                    for (deriv_index, e) in expected.iter().enumerate() {
+                        assert_relative_eq!(
+                            matrix[[range_dim * target_index + deriv_index, source_index]],
+                            *e,
+                            epsilon = 1E-12
+                        );
+                    }
+                }
+            }
+
+            // Applied to charges the matrix gives the same as `evaluate_st`.
+            let mut expected = vec![0.0; range_dim * ntargets];
+            kernel.evaluate_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                charges.data(),
+                &mut expected,
+            );
+
+            for (row, e) in expected.iter().enumerate() {
+                let actual = (0..nsources)
+                    .map(|col| matrix[[row, col]] * charges[[col]])
+                    .sum::<f64>();
+                assert_relative_eq!(actual, *e, epsilon = 1E-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_assemble_adjoint_double_layer_laplace_3d() {
+        let nsources = 4;
+        let ntargets = 6;
+
+        let mut sources = rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut targets = rlst_dynamic_array2!(f64, [ntargets, 3]);
+        let mut normals = rlst_dynamic_array2!(f64, [ntargets, 3]);
+
+        sources.fill_from_seed_equally_distributed(1);
+        targets.fill_from_seed_equally_distributed(2);
+        normals.fill_from_seed_equally_distributed(3);
+
+        for target_index in 0..ntargets {
+            let norm = (normals[[target_index, 0]].square()
+                + normals[[target_index, 1]].square()
+                + normals[[target_index, 2]].square())
+            .sqrt();
+            for component in 0..3 {
+                normals[[target_index, component]] /= norm;
+            }
+        }
+
+        let mut green_value_t = rlst_dynamic_array2!(f64, [nsources, ntargets]);
+
+        Laplace3dKernel::<f64>::default().assemble_adjoint_double_layer_st(
+            sources.data(),
+            targets.data(),
+            normals.data(),
+            green_value_t.data_mut(),
+        );
+
+        for target_index in 0..ntargets {
+            for source_index in 0..nsources {
+                let diff = [
+                    targets[[target_index, 0]] - sources[[source_index, 0]],
+                    targets[[target_index, 1]] - sources[[source_index, 1]],
+                    targets[[target_index, 2]] - sources[[source_index, 2]],
+                ];
+                let dist = (diff[0].square() + diff[1].square() + diff[2].square()).sqrt();
+                let normal_dot_diff = diff[0] * normals[[target_index, 0]]
+                    + diff[1] * normals[[target_index, 1]]
+                    + diff[2] * normals[[target_index, 2]];
+
+                let expected = -0.25 * f64::FRAC_1_PI() * normal_dot_diff / dist.powi(3);
+
+                assert_relative_eq!(
+                    green_value_t[[source_index, target_index]],
+                    expected,
+                    epsilon = 1E-12
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_assemble_symmetric_laplace_3d() {
+        let npoints = 7;
+
+        let mut points = rlst_dynamic_array2!(f64, [npoints, 3]);
+        points.fill_from_seed_equally_distributed(1);
+
+        let mut expected = rlst_dynamic_array2!(f64, [npoints, npoints]);
+        let mut actual = rlst_dynamic_array2!(f64, [npoints, npoints]);
+
+        Laplace3dKernel::<f64>::default().assemble_st(
+            EvalType::Value,
+            points.data(),
+            points.data(),
+            expected.data_mut(),
+        );
+
+        Laplace3dKernel::<f64>::default().assemble_symmetric_st(points.data(), actual.data_mut());
+
+        for row in 0..npoints {
+            for col in 0..npoints {
+                assert_relative_eq!(actual[[row, col]], expected[[row, col]], epsilon = 1E-14);
+            }
+        }
+    }
+
     #[test]
     fn test_assemble_diag_laplace_3d() {
         let nsources = 5;
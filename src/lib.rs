@@ -6,5 +6,7 @@
 pub mod helmholtz_3d;
 pub mod helpers;
 pub mod laplace_3d;
+pub mod sum_kernel;
 pub mod traits;
 pub mod types;
+pub mod writer;
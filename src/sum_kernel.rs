@@ -0,0 +1,433 @@
+//! Sum of two kernels
+use crate::traits::Kernel;
+use crate::types::EvalType;
+use rlst::RlstScalar;
+
+/// Length of the stack buffer used in [SumKernel::greens_fct].
+const GREENS_FCT_BUFFER_LEN: usize = 4;
+
+/// Kernel that is the sum of two kernels with the same scalar type.
+///
+/// The two kernels must agree in their space dimension and in their domain and range
+/// component counts. This is checked when the kernel is created.
+#[derive(Clone)]
+pub struct SumKernel<A: Kernel, B: Kernel<T = A::T>> {
+    first: A,
+    second: B,
+}
+
+impl<A: Kernel, B: Kernel<T = A::T>> SumKernel<A, B> {
+    /// Create new
+    pub fn new(first: A, second: B) -> Self {
+        assert_eq!(
+            first.space_dimension(),
+            second.space_dimension(),
+            "Space dimensions of kernels do not match. {} != {} ",
+            first.space_dimension(),
+            second.space_dimension(),
+        );
+
+        assert_eq!(
+            first.domain_component_count(),
+            second.domain_component_count(),
+            "Domain component counts of kernels do not match. {} != {} ",
+            first.domain_component_count(),
+            second.domain_component_count(),
+        );
+
+        for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+            assert_eq!(
+                first.range_component_count(eval_type),
+                second.range_component_count(eval_type),
+                "Range component counts of kernels do not match. {} != {} ",
+                first.range_component_count(eval_type),
+                second.range_component_count(eval_type),
+            );
+        }
+
+        Self { first, second }
+    }
+
+    /// The first kernel of the sum
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// The second kernel of the sum
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: Kernel, B: Kernel<T = A::T>> Kernel for SumKernel<A, B> {
+    type T = A::T;
+
+    fn greens_fct(
+        &self,
+        eval_type: EvalType,
+        source: &[<Self::T as RlstScalar>::Real],
+        target: &[<Self::T as RlstScalar>::Real],
+        result: &mut [Self::T],
+    ) {
+        self.first.greens_fct(eval_type, source, target, result);
+
+        // Avoid a heap allocation per pair for range component counts of up to 4,
+        // which covers values and derivatives in three dimensions.
+        if result.len() <= GREENS_FCT_BUFFER_LEN {
+            let mut buffer = [<Self::T as num::Zero>::zero(); GREENS_FCT_BUFFER_LEN];
+            let second_result = &mut buffer[..result.len()];
+            self.second
+                .greens_fct(eval_type, source, target, second_result);
+            add_assign(result, second_result);
+        } else {
+            let mut second_result = zeros(result.len());
+            self.second
+                .greens_fct(eval_type, source, target, &mut second_result);
+            add_assign(result, &second_result);
+        }
+    }
+
+    fn evaluate_st(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        targets: &[<Self::T as RlstScalar>::Real],
+        charges: &[Self::T],
+        result: &mut [Self::T],
+    ) {
+        let mut second_result = zeros(result.len());
+        self.first
+            .evaluate_st(eval_type, sources, targets, charges, result);
+        self.second
+            .evaluate_st(eval_type, sources, targets, charges, &mut second_result);
+        add_assign(result, &second_result);
+    }
+
+    fn evaluate_mt(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        targets: &[<Self::T as RlstScalar>::Real],
+        charges: &[Self::T],
+        result: &mut [Self::T],
+    ) {
+        let mut second_result = zeros(result.len());
+        self.first
+            .evaluate_mt(eval_type, sources, targets, charges, result);
+        self.second
+            .evaluate_mt(eval_type, sources, targets, charges, &mut second_result);
+        add_assign(result, &second_result);
+    }
+
+    fn assemble_st(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        targets: &[<Self::T as RlstScalar>::Real],
+        result: &mut [Self::T],
+    ) {
+        let mut second_result = zeros(result.len());
+        self.first.assemble_st(eval_type, sources, targets, result);
+        self.second
+            .assemble_st(eval_type, sources, targets, &mut second_result);
+        add_assign(result, &second_result);
+    }
+
+    fn assemble_symmetric_st(
+        &self,
+        points: &[<Self::T as RlstScalar>::Real],
+        result: &mut [Self::T],
+    ) {
+        let mut second_result = zeros(result.len());
+        self.first.assemble_symmetric_st(points, result);
+        self.second
+            .assemble_symmetric_st(points, &mut second_result);
+        add_assign(result, &second_result);
+    }
+
+    fn assemble_mt(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        targets: &[<Self::T as RlstScalar>::Real],
+        result: &mut [Self::T],
+    ) {
+        let mut second_result = zeros(result.len());
+        self.first.assemble_mt(eval_type, sources, targets, result);
+        self.second
+            .assemble_mt(eval_type, sources, targets, &mut second_result);
+        add_assign(result, &second_result);
+    }
+
+    fn assemble_diagonal_st(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        targets: &[<Self::T as RlstScalar>::Real],
+        result: &mut [Self::T],
+    ) {
+        let mut second_result = zeros(result.len());
+        self.first
+            .assemble_diagonal_st(eval_type, sources, targets, result);
+        self.second
+            .assemble_diagonal_st(eval_type, sources, targets, &mut second_result);
+        add_assign(result, &second_result);
+    }
+
+    fn domain_component_count(&self) -> usize {
+        self.first.domain_component_count()
+    }
+
+    fn space_dimension(&self) -> usize {
+        self.first.space_dimension()
+    }
+
+    fn range_component_count(&self, eval_type: EvalType) -> usize {
+        self.first.range_component_count(eval_type)
+    }
+}
+
+fn zeros<T: RlstScalar>(len: usize) -> Vec<T> {
+    vec![T::zero(); len]
+}
+
+fn add_assign<T: RlstScalar>(result: &mut [T], other: &[T]) {
+    for (value, other_value) in result.iter_mut().zip(other) {
+        *value += *other_value;
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::laplace_3d::Laplace3dKernel;
+    use approx::assert_relative_eq;
+    use rlst::{rlst_dynamic_array1, rlst_dynamic_array2, RawAccess, RawAccessMut};
+
+    #[test]
+    fn test_sum_of_laplace_kernels() {
+        let nsources = 5;
+        let ntargets = 3;
+
+        let mut sources = rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut targets = rlst_dynamic_array2!(f64, [ntargets, 3]);
+        let mut charges = rlst_dynamic_array1!(f64, [nsources]);
+
+        sources.fill_from_seed_equally_distributed(0);
+        targets.fill_from_seed_equally_distributed(1);
+        charges.fill_from_seed_equally_distributed(2);
+
+        let laplace = Laplace3dKernel::<f64>::new();
+        let kernel = SumKernel::new(Laplace3dKernel::<f64>::new(), Laplace3dKernel::<f64>::new());
+
+        for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+            let range_dim = laplace.range_component_count(eval_type);
+
+            let mut expected = rlst_dynamic_array1!(f64, [range_dim * ntargets]);
+            let mut actual = rlst_dynamic_array1!(f64, [range_dim * ntargets]);
+
+            laplace.evaluate_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                charges.data(),
+                expected.data_mut(),
+            );
+            kernel.evaluate_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                charges.data(),
+                actual.data_mut(),
+            );
+
+            for (a, e) in actual.data().iter().zip(expected.data()) {
+                assert_relative_eq!(*a, 2.0 * e, epsilon = 1E-12);
+            }
+
+            let mut expected = rlst_dynamic_array1!(f64, [range_dim * ntargets * nsources]);
+            let mut actual = rlst_dynamic_array1!(f64, [range_dim * ntargets * nsources]);
+
+            laplace.assemble_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                expected.data_mut(),
+            );
+            kernel.assemble_st(eval_type, sources.data(), targets.data(), actual.data_mut());
+
+            for (a, e) in actual.data().iter().zip(expected.data()) {
+                assert_relative_eq!(*a, 2.0 * e, epsilon = 1E-12);
+            }
+
+            let mut scales = rlst_dynamic_array1!(f64, [ntargets]);
+            scales.fill_from_seed_equally_distributed(3);
+
+            kernel.assemble_st_scaled(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                scales.data(),
+                actual.data_mut(),
+            );
+
+            for (target_index, (a, e)) in actual
+                .data()
+                .chunks_exact(range_dim * nsources)
+                .zip(expected.data().chunks_exact(range_dim * nsources))
+                .enumerate()
+            {
+                for (a, e) in a.iter().zip(e) {
+                    assert_relative_eq!(*a, 2.0 * e * scales[[target_index]], epsilon = 1E-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sum_of_laplace_kernels_mt_and_diagonal() {
+        let nsources = 5;
+        let ntargets = 5;
+
+        let mut sources = rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut targets = rlst_dynamic_array2!(f64, [ntargets, 3]);
+        let mut charges = rlst_dynamic_array1!(f64, [nsources]);
+
+        sources.fill_from_seed_equally_distributed(0);
+        targets.fill_from_seed_equally_distributed(1);
+        charges.fill_from_seed_equally_distributed(2);
+
+        let laplace = Laplace3dKernel::<f64>::new();
+        let kernel = SumKernel::new(Laplace3dKernel::<f64>::new(), Laplace3dKernel::<f64>::new());
+
+        for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+            let range_dim = laplace.range_component_count(eval_type);
+
+            let mut expected = rlst_dynamic_array1!(f64, [range_dim * ntargets]);
+            let mut actual = rlst_dynamic_array1!(f64, [range_dim * ntargets]);
+
+            laplace.evaluate_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                charges.data(),
+                expected.data_mut(),
+            );
+            kernel.evaluate_mt(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                charges.data(),
+                actual.data_mut(),
+            );
+
+            for (a, e) in actual.data().iter().zip(expected.data()) {
+                assert_relative_eq!(*a, 2.0 * e, epsilon = 1E-12);
+            }
+
+            let mut expected = rlst_dynamic_array1!(f64, [range_dim * ntargets]);
+            let mut actual = rlst_dynamic_array1!(f64, [range_dim * ntargets]);
+
+            laplace.assemble_diagonal_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                expected.data_mut(),
+            );
+            kernel.assemble_diagonal_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                actual.data_mut(),
+            );
+
+            for (a, e) in actual.data().iter().zip(expected.data()) {
+                assert_relative_eq!(*a, 2.0 * e, epsilon = 1E-12);
+            }
+
+            let mut expected = vec![0.0; range_dim];
+            let mut actual = vec![0.0; range_dim];
+
+            laplace.greens_fct(
+                eval_type,
+                &[sources[[0, 0]], sources[[0, 1]], sources[[0, 2]]],
+                &[targets[[0, 0]], targets[[0, 1]], targets[[0, 2]]],
+                &mut expected,
+            );
+            kernel.greens_fct(
+                eval_type,
+                &[sources[[0, 0]], sources[[0, 1]], sources[[0, 2]]],
+                &[targets[[0, 0]], targets[[0, 1]], targets[[0, 2]]],
+                &mut actual,
+            );
+
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                assert_relative_eq!(*a, 2.0 * e, epsilon = 1E-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sum_of_laplace_kernels_accumulates() {
+        let nsources = 5;
+        let ntargets = 3;
+
+        let mut sources = rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut targets = rlst_dynamic_array2!(f64, [ntargets, 3]);
+        let mut charges = rlst_dynamic_array1!(f64, [nsources]);
+
+        sources.fill_from_seed_equally_distributed(0);
+        targets.fill_from_seed_equally_distributed(1);
+        charges.fill_from_seed_equally_distributed(2);
+
+        let laplace = Laplace3dKernel::<f64>::new();
+        let kernel = SumKernel::new(Laplace3dKernel::<f64>::new(), Laplace3dKernel::<f64>::new());
+
+        for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+            let range_dim = laplace.range_component_count(eval_type);
+
+            let mut initial = rlst_dynamic_array1!(f64, [range_dim * ntargets]);
+            initial.fill_from_seed_equally_distributed(3);
+
+            let mut expected = rlst_dynamic_array1!(f64, [range_dim * ntargets]);
+            laplace.evaluate_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                charges.data(),
+                expected.data_mut(),
+            );
+
+            let mut actual_st = rlst_dynamic_array1!(f64, [range_dim * ntargets]);
+            let mut actual_mt = rlst_dynamic_array1!(f64, [range_dim * ntargets]);
+            actual_st.data_mut().copy_from_slice(initial.data());
+            actual_mt.data_mut().copy_from_slice(initial.data());
+
+            kernel.evaluate_st(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                charges.data(),
+                actual_st.data_mut(),
+            );
+            kernel.evaluate_mt(
+                eval_type,
+                sources.data(),
+                targets.data(),
+                charges.data(),
+                actual_mt.data_mut(),
+            );
+
+            for ((st, mt), (i, e)) in actual_st
+                .data()
+                .iter()
+                .zip(actual_mt.data())
+                .zip(initial.data().iter().zip(expected.data()))
+            {
+                assert_relative_eq!(*st, i + 2.0 * e, epsilon = 1E-12);
+                assert_relative_eq!(*mt, i + 2.0 * e, epsilon = 1E-12);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Trait for Green's function kernels
-use crate::types::EvalType;
-use rlst::RlstScalar;
+use crate::types::{DynamicArray2, EvalType};
+use rlst::{rlst_dynamic_array2, RawAccessMut, RlstScalar};
 
 /// Interface to evaluating Green's functions for given sources and targets.
 pub trait Kernel: Sync {
@@ -40,6 +40,34 @@ pub trait Kernel: Sync {
         result: &mut [Self::T],
     );
 
+    /// Single threaded evaluation of Green's functions for several sets of targets.
+    ///
+    /// The same sources and charges are evaluated at each set of targets in `query_sets`
+    /// and the result for the i-th set is written into `outs[i]`. The arguments have the same
+    /// meaning and layout as for [Kernel::evaluate_st]. The default implementation calls
+    /// [Kernel::evaluate_st] for each set. Kernels override it to prepare the sources and
+    /// charges only once for all sets.
+    fn evaluate_st_multi_query(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        charges: &[Self::T],
+        query_sets: &[&[<Self::T as RlstScalar>::Real]],
+        outs: &mut [&mut [Self::T]],
+    ) {
+        assert_eq!(
+            query_sets.len(),
+            outs.len(),
+            "Number of query sets and outputs do not match. {} != {} ",
+            query_sets.len(),
+            outs.len(),
+        );
+
+        for (targets, result) in query_sets.iter().zip(outs.iter_mut()) {
+            self.evaluate_st(eval_type, sources, targets, charges, result);
+        }
+    }
+
     /// Multi-threaded evaluation of a Green's function kernel.
     ///
     /// The method parallelizes over the given targets. It expects a Rayon `ThreadPool`
@@ -75,6 +103,69 @@ pub trait Kernel: Sync {
         result: &mut [Self::T],
     );
 
+    /// Single threaded assembly of a kernel matrix with a scaling factor for each target.
+    ///
+    /// This is equivalent to [Kernel::assemble_st] followed by multiplying all entries that belong
+    /// to a target with the corresponding entry of `target_scales`. The default implementation
+    /// does exactly that. Kernels can override it to apply the scaling while each target is
+    /// assembled instead of in a separate pass over `result`.
+    ///
+    /// - `target_scales`: A slice defining the scaling factors. For each target point there needs
+    ///   to be one scaling factor. For [EvalType::ValueDeriv] both the values and
+    ///   the derivatives are scaled.
+    ///
+    /// The other arguments are the same as for [Kernel::assemble_st].
+    fn assemble_st_scaled(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        targets: &[<Self::T as RlstScalar>::Real],
+        target_scales: &[Self::T],
+        result: &mut [Self::T],
+    ) {
+        let nsources = sources.len() / self.space_dimension();
+        let ntargets = targets.len() / self.space_dimension();
+        let range_dim = self.range_component_count(eval_type);
+
+        assert_eq!(
+            target_scales.len(),
+            ntargets,
+            "Wrong dimension for `target_scales`. {} != {} ",
+            target_scales.len(),
+            ntargets,
+        );
+
+        self.assemble_st(eval_type, sources, targets, result);
+
+        if nsources == 0 {
+            return;
+        }
+
+        for (my_chunk, scale) in result
+            .chunks_exact_mut(range_dim * nsources)
+            .zip(target_scales)
+        {
+            my_chunk.iter_mut().for_each(|value| *value *= *scale);
+        }
+    }
+
+    /// Single threaded assembly of the kernel matrix of a set of points with itself.
+    ///
+    /// The result is the same as for [Kernel::assemble_st] with [EvalType::Value] and `points`
+    /// used both as sources and targets. Kernels whose matrix is symmetric in this case override
+    /// this method to compute only the upper triangle and mirror it, which halves the number
+    /// of kernel evaluations.
+    ///
+    /// - `points`: The points, used both as sources and targets, in the same layout as for [Kernel::assemble_st].
+    /// - `result`: The result array. It has the same layout as for [Kernel::assemble_st] with [EvalType::Value].
+    fn assemble_symmetric_st(
+        &self,
+        points: &[<Self::T as RlstScalar>::Real],
+        result: &mut [Self::T],
+    ) {
+        self.assemble_st(EvalType::Value, points, points, result);
+    }
+
     /// Multi-threaded version of kernel matrix assembly.
     fn assemble_mt(
         &self,
@@ -84,6 +175,51 @@ pub trait Kernel: Sync {
         result: &mut [Self::T],
     );
 
+    /// Single threaded assembly of a kernel matrix between two point sets, returned as a matrix.
+    ///
+    /// This is a convenience wrapper around [Kernel::assemble_st] for assembling the interaction
+    /// matrix between two sets of points, such as two discretised surfaces. The returned matrix
+    /// has one row per target and derivative component and one column per source, that is
+    /// the entry `[r * i + j, k]` is the component `j` of the interaction of source `k` with target `i`,
+    /// where `r` is the [range component count](Kernel::range_component_count). Multiplying the matrix
+    /// with a vector of charges gives the result of [Kernel::evaluate_st].
+    ///
+    /// The targets are assembled one at a time into a buffer of a single row, so apart from the
+    /// returned matrix no storage of the size of the full matrix is allocated.
+    fn assemble_surface_to_surface(
+        &self,
+        eval_type: EvalType,
+        sources: &[<Self::T as RlstScalar>::Real],
+        targets: &[<Self::T as RlstScalar>::Real],
+    ) -> DynamicArray2<Self::T> {
+        let dim = self.space_dimension();
+        let nsources = sources.len() / dim;
+        let ntargets = targets.len() / dim;
+        let range_dim = self.range_component_count(eval_type);
+        let nrows = range_dim * ntargets;
+
+        let mut matrix = rlst_dynamic_array2!(Self::T, [nrows, nsources]);
+        let mut target = vec![<<Self::T as RlstScalar>::Real as num::Zero>::zero(); dim];
+        let mut row = vec![<Self::T as num::Zero>::zero(); range_dim * nsources];
+
+        let data = matrix.data_mut();
+        for target_index in 0..ntargets {
+            for (component, value) in target.iter_mut().enumerate() {
+                *value = targets[component * ntargets + target_index];
+            }
+
+            self.assemble_st(eval_type, sources, &target, &mut row);
+
+            // The matrix is stored column-major with one column per source.
+            for (deriv_index, values) in row.chunks_exact(nsources.max(1)).enumerate() {
+                for (source_index, value) in values.iter().enumerate() {
+                    data[source_index * nrows + range_dim * target_index + deriv_index] = *value;
+                }
+            }
+        }
+        matrix
+    }
+
     /// Single threaded assembly of the diagonal of a kernel matrix
     fn assemble_diagonal_st(
         &self,
@@ -1,4 +1,5 @@
 //! Type definitions
+use rlst::{Array, BaseArray, VectorContainer};
 
 /// Evaluation Mode
 #[derive(Clone, Copy)]
@@ -8,3 +9,6 @@ pub enum EvalType {
     /// Both values and derivatives required
     ValueDeriv,
 }
+
+/// Dynamically allocated two-dimensional array
+pub type DynamicArray2<T> = Array<T, BaseArray<T, VectorContainer<T>, 2>, 2>;
@@ -0,0 +1,187 @@
+//! Streaming assembly of kernel matrices
+use crate::traits::Kernel;
+use crate::types::EvalType;
+use rlst::{c32, c64, RlstScalar};
+use std::io::Write;
+
+/// Scalar types that can be written as native-endian bytes.
+///
+/// Complex numbers are written as their real part followed by their imaginary part.
+pub trait NativeBytes: Copy {
+    /// Append the native-endian bytes of `self` to `bytes`.
+    fn extend_ne_bytes(&self, bytes: &mut Vec<u8>);
+}
+
+impl NativeBytes for f32 {
+    fn extend_ne_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_ne_bytes());
+    }
+}
+
+impl NativeBytes for f64 {
+    fn extend_ne_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_ne_bytes());
+    }
+}
+
+impl NativeBytes for c32 {
+    fn extend_ne_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.re.to_ne_bytes());
+        bytes.extend_from_slice(&self.im.to_ne_bytes());
+    }
+}
+
+impl NativeBytes for c64 {
+    fn extend_ne_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.re.to_ne_bytes());
+        bytes.extend_from_slice(&self.im.to_ne_bytes());
+    }
+}
+
+/// Assemble a kernel matrix in blocks of targets and stream it into a writer.
+///
+/// The matrix is assembled `block_rows` targets at a time with [Kernel::assemble_st] and
+/// each block is written to `writer` as native-endian bytes. The written data has
+/// exactly the memory layout of the `result` array of [Kernel::assemble_st] for all targets,
+/// so only a single block of the matrix needs to be held in memory at any time.
+///
+/// - `sources`: The source points in the same layout as for [Kernel::assemble_st].
+/// - `targets`: The target points in the same layout as for [Kernel::assemble_st].
+/// - `block_rows`: The number of targets assembled per block.
+pub fn assemble_dense_to_writer<K: Kernel, W: Write>(
+    kernel: &K,
+    eval_type: EvalType,
+    sources: &[<K::T as RlstScalar>::Real],
+    targets: &[<K::T as RlstScalar>::Real],
+    writer: &mut W,
+    block_rows: usize,
+) -> std::io::Result<()>
+where
+    K::T: NativeBytes,
+{
+    assert!(block_rows > 0, "`block_rows` must be positive.");
+
+    assert!(
+        sources.len() % kernel.space_dimension() == 0,
+        "Length of sources {} is not a multiple of space dimension {}.",
+        sources.len(),
+        kernel.space_dimension()
+    );
+
+    assert!(
+        targets.len() % kernel.space_dimension() == 0,
+        "Length of targets {} is not a multiple of space dimension {}.",
+        targets.len(),
+        kernel.space_dimension()
+    );
+
+    let dim = kernel.space_dimension();
+    let nsources = sources.len() / dim;
+    let ntargets = targets.len() / dim;
+    let row_len = kernel.range_component_count(eval_type) * nsources;
+    let block_rows = usize::min(block_rows, ntargets);
+
+    let mut block_targets = Vec::with_capacity(dim * block_rows);
+    let mut buffer = vec![<K::T as num::Zero>::zero(); row_len * block_rows];
+    let mut bytes = Vec::with_capacity(std::mem::size_of_val(buffer.as_slice()));
+
+    for start in (0..ntargets).step_by(block_rows.max(1)) {
+        let end = usize::min(start + block_rows, ntargets);
+
+        block_targets.clear();
+        for component in 0..dim {
+            block_targets.extend_from_slice(
+                &targets[component * ntargets + start..component * ntargets + end],
+            );
+        }
+
+        let block = &mut buffer[..row_len * (end - start)];
+        kernel.assemble_st(eval_type, sources, &block_targets, block);
+
+        bytes.clear();
+        for value in block.iter() {
+            value.extend_ne_bytes(&mut bytes);
+        }
+        writer.write_all(&bytes)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::helmholtz_3d::Helmholtz3dKernel;
+    use crate::laplace_3d::Laplace3dKernel;
+    use rlst::{rlst_dynamic_array2, RawAccess};
+
+    #[test]
+    fn test_assemble_dense_to_writer() {
+        let nsources = 4;
+        let ntargets = 7;
+
+        let mut sources = rlst_dynamic_array2!(f64, [nsources, 3]);
+        let mut targets = rlst_dynamic_array2!(f64, [ntargets, 3]);
+
+        sources.fill_from_seed_equally_distributed(1);
+        targets.fill_from_seed_equally_distributed(2);
+
+        let laplace = Laplace3dKernel::<f64>::new();
+        let helmholtz = Helmholtz3dKernel::<c64>::new(1.5);
+
+        for eval_type in [EvalType::Value, EvalType::ValueDeriv] {
+            let range_dim = laplace.range_component_count(eval_type);
+
+            // A block size larger than the number of targets writes everything in one block.
+            for block_rows in [3, 100] {
+                let mut expected = vec![0.0; range_dim * nsources * ntargets];
+                laplace.assemble_st(eval_type, sources.data(), targets.data(), &mut expected);
+
+                let mut bytes = Vec::<u8>::new();
+                assemble_dense_to_writer(
+                    &laplace,
+                    eval_type,
+                    sources.data(),
+                    targets.data(),
+                    &mut bytes,
+                    block_rows,
+                )
+                .unwrap();
+
+                let actual = bytes
+                    .chunks_exact(std::mem::size_of::<f64>())
+                    .map(|chunk| f64::from_ne_bytes(chunk.try_into().unwrap()))
+                    .collect::<Vec<_>>();
+
+                assert_eq!(actual, expected);
+            }
+
+            let mut expected = vec![c64::default(); range_dim * nsources * ntargets];
+            helmholtz.assemble_st(eval_type, sources.data(), targets.data(), &mut expected);
+
+            let mut bytes = Vec::<u8>::new();
+            assemble_dense_to_writer(
+                &helmholtz,
+                eval_type,
+                sources.data(),
+                targets.data(),
+                &mut bytes,
+                3,
+            )
+            .unwrap();
+
+            let actual = bytes
+                .chunks_exact(2 * std::mem::size_of::<f64>())
+                .map(|chunk| {
+                    c64::new(
+                        f64::from_ne_bytes(chunk[..8].try_into().unwrap()),
+                        f64::from_ne_bytes(chunk[8..].try_into().unwrap()),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual, expected);
+        }
+    }
+}